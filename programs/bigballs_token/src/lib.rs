@@ -2,11 +2,104 @@
 mod tests;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
-use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token};
+use anchor_spl::token_2022::Token2022;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_spl::token_interface;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_token_2022::extension::{
+    transfer_fee, transfer_hook, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
 
 declare_id!("HSP1yi2aHiKBpYVKv6QkURWSjMhr5WEz3xYdZktBFGVd");
 
+// Fixed-point scale for the reflection reward-per-token accumulator
+pub const REFLECTION_PRECISION: u128 = 1_000_000_000_000;
+
+// Maximum number of AMM pool token accounts that can be registered
+pub const MAX_AMM_POOLS: usize = 4;
+
+// Length of the rolling buy/sell volume window (24 hours), tracked in hourly
+// buckets: the current hour plus the 24 before it
+pub const SECONDS_PER_DAY: i64 = 86_400;
+pub const SECONDS_PER_HOUR: i64 = 3_600;
+pub const VOLUME_WINDOW_BUCKETS: usize = 25;
+
+// Floor for transaction and wallet limits (0.1% of supply) so trading can't be bricked
+pub const MIN_LIMIT_BP: u64 = 10;
+
+// Longest launch phase the authority can configure
+pub const MAX_LAUNCH_PHASE_DURATION: i64 = 7 * SECONDS_PER_DAY;
+
+// Extra accounts resolved by Token-2022 for the transfer hook, in order: config,
+// source holder state, owner blocklist entry, destination holder state,
+// destination owner's blocklist entry and exemption, and owner exemption
+pub const TRANSFER_HOOK_EXTRA_ACCOUNTS: usize = 7;
+
+// Maximum number of allocation buckets in the initial distribution
+pub const MAX_ALLOCATION_BUCKETS: usize = 8;
+
+// Maximum size of the marketing multisig signer set
+pub const MAX_MARKETING_SIGNERS: usize = 10;
+
+// Floor for the marketing timelock; spends above its threshold always wait it out
+pub const MIN_MARKETING_TIMELOCK: i64 = SECONDS_PER_DAY;
+
+// Notice period before a scheduled manual burn can execute
+pub const MANUAL_BURN_NOTICE: i64 = SECONDS_PER_DAY;
+
+// Window after the notice in which a scheduled burn must execute; a stale
+// burn no longer blocks scheduling the next one
+pub const MANUAL_BURN_EXPIRY: i64 = 7 * SECONDS_PER_DAY;
+
+// Cap per manual burn, in basis points of total supply
+pub const MAX_MANUAL_BURN_BP: u64 = 100;
+
+// Price observation ring buffer size and minimum spacing; together they
+// cover one hour of history
+pub const PRICE_OBSERVATIONS: usize = 24;
+pub const MIN_OBSERVATION_INTERVAL: i64 = 150;
+
+// Consecutive observations a price must hold for before the breaker reacts,
+// so one manipulated reading (e.g. a flash swap around the crank) can't trip it
+pub const CIRCUIT_BREAKER_CONFIRMATIONS: usize = 3;
+
+// Fixed-point scale for pool prices (quote per token)
+pub const PRICE_PRECISION: u128 = 1_000_000_000_000;
+
+// Circuit breaker pauses expire automatically after at most this long
+pub const MAX_PAUSE_DURATION: i64 = SECONDS_PER_DAY;
+
+// Maximum size of the emergency guardian set and reason URI length
+pub const MAX_GUARDIANS: usize = 7;
+pub const MAX_REASON_URI_LEN: usize = 200;
+
+// Emergency actions need at least 3-of-5 guardians behind a 24h timelock, and
+// must execute within a week of becoming executable
+pub const MIN_GUARDIANS: usize = 5;
+pub const MIN_GUARDIAN_THRESHOLD: u8 = 3;
+pub const MIN_GUARDIAN_TIMELOCK: i64 = SECONDS_PER_DAY;
+pub const EMERGENCY_ACTION_EXPIRY: i64 = 7 * SECONDS_PER_DAY;
+
+// Upper bounds for the post-launch anti-sniper window and penalty fee
+pub const MAX_ANTI_SNIPER_SLOTS: u64 = 150;
+pub const MAX_ANTI_SNIPER_FEE_BP: u16 = 5000;
+
+// Pause flags; each bit independently halts one class of activity
+pub const PAUSE_BUYS: u8 = 1 << 0;
+pub const PAUSE_SELLS: u8 = 1 << 1;
+pub const PAUSE_TRANSFERS: u8 = 1 << 2;
+pub const PAUSE_REFLECTION_CLAIMS: u8 = 1 << 3;
+pub const PAUSE_GOVERNANCE: u8 = 1 << 4;
+pub const PAUSE_TRADING: u8 = PAUSE_BUYS | PAUSE_SELLS | PAUSE_TRANSFERS;
+pub const PAUSE_ALL: u8 = PAUSE_TRADING | PAUSE_REFLECTION_CLAIMS | PAUSE_GOVERNANCE;
+
+// Reason codes recorded when trading is paused
+pub const PAUSE_REASON_NONE: u8 = 0;
+pub const PAUSE_REASON_PRICE_DROP: u8 = 1;
+
 #[program]
 pub mod bigballs_token {
     use super::*;
@@ -17,40 +110,16 @@ pub mod bigballs_token {
         symbol: String,
         decimals: u8,
         marketing_wallet: Pubkey,
+        dev_wallet: Pubkey,
     ) -> Result<()> {
-        // Validate marketing wallet
-        require!(
-            marketing_wallet != Pubkey::default(),
-            BigBallsError::InvalidMarketingWallet
-        );
-
         let config = &mut ctx.accounts.config;
-        
-        // Basic token configuration
-        config.name = name;
-        config.symbol = symbol;
-        config.decimals = decimals;
-        config.total_supply = 1_000_000_000 * 10u64.pow(decimals as u32);  // 1 billion tokens
+        config.init_defaults(name, symbol, decimals, marketing_wallet, dev_wallet)?;
         config.authority = ctx.accounts.authority.key();
-        config.marketing_wallet = marketing_wallet;
-        
-        // Trading parameters
-        config.trading_enabled = false;
-        config.max_transaction_amount = config.total_supply / 1000; // 0.1% of total supply
-        config.max_wallet_amount = config.total_supply / 100;      // 1% of total supply
-        
-        // Fee configuration (in basis points)
-        config.reflection_fee_bp = 200;     // 2%
-        config.marketing_fee_bp = 150;      // 1.5%
-        config.burn_fee_bp = 100;           // 1%
-        config.dev_fee_bp = 50;             // 0.5%
-        
-        // Cooldown periods (in seconds)
-        config.buy_cooldown = 300;          // 5 minutes
-        config.sell_cooldown = 1800;        // 30 minutes
-        config.transaction_cooldown = 60;    // 1 minute
+        config.mint = ctx.accounts.mint.key();
+        config.token_program = ctx.accounts.token_program.key();
         
-        // Initialize the mint
+        // Initialize the mint; only the program PDA can ever mint or freeze
+        let mint_authority = ctx.accounts.mint_authority.key();
         token::initialize_mint(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
@@ -60,8 +129,8 @@ pub mod bigballs_token {
                 },
             ),
             decimals,
-            &config.authority,
-            Some(&config.authority),
+            &mint_authority,
+            Some(&mint_authority),
         )?;
 
         emit!(TokenInitialized {
@@ -70,43 +139,248 @@ pub mod bigballs_token {
             total_supply: config.total_supply,
             decimals,
             marketing_wallet,
+            dev_wallet,
+        });
+
+        Ok(())
+    }
+
+    pub fn initialize_token_2022(
+        ctx: Context<InitializeToken2022>,
+        name: String,
+        symbol: String,
+        decimals: u8,
+        marketing_wallet: Pubkey,
+        dev_wallet: Pubkey,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.init_defaults(name, symbol, decimals, marketing_wallet, dev_wallet)?;
+        config.authority = ctx.accounts.authority.key();
+        config.mint = ctx.accounts.mint.key();
+        config.token_program = ctx.accounts.token_program.key();
+
+        let token_program_id = ctx.accounts.token_program.key();
+        let mint_key = ctx.accounts.mint.key();
+        let fee_authority = ctx.accounts.fee_authority.key();
+        let transfer_fee_bp = config.total_fee_bp();
+
+        // Allocate the mint with room for the TransferFee and TransferHook extensions
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::TransferHook,
+        ])?;
+        system_program::create_account(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &token_program_id,
+        )?;
+
+        // Fees are withheld on every transfer and only the fee PDA can withdraw them
+        invoke(
+            &transfer_fee::instruction::initialize_transfer_fee_config(
+                &token_program_id,
+                &mint_key,
+                Some(&fee_authority),
+                Some(&fee_authority),
+                transfer_fee_bp,
+                u64::MAX,
+            )?,
+            &[ctx.accounts.mint.to_account_info()],
+        )?;
+
+        // Route every transfer of the mint through this program's hook; with no
+        // hook authority the program can never be swapped out
+        invoke(
+            &transfer_hook::instruction::initialize(
+                &token_program_id,
+                &mint_key,
+                None,
+                Some(crate::ID),
+            )?,
+            &[ctx.accounts.mint.to_account_info()],
+        )?;
+
+        // Only the program PDA can ever mint or freeze
+        let mint_authority = ctx.accounts.mint_authority.key();
+        invoke(
+            &spl_token_2022::instruction::initialize_mint2(
+                &token_program_id,
+                &mint_key,
+                &mint_authority,
+                Some(&mint_authority),
+                decimals,
+            )?,
+            &[ctx.accounts.mint.to_account_info()],
+        )?;
+
+        emit!(TokenInitialized {
+            mint: mint_key,
+            authority: config.authority,
+            total_supply: config.total_supply,
+            decimals,
+            marketing_wallet,
+            dev_wallet,
+        });
+
+        emit!(TransferExtensionsInitialized {
+            mint: mint_key,
+            transfer_fee_bp,
+            fee_authority,
+            transfer_hook_program: crate::ID,
         });
 
         Ok(())
     }
 
-    pub fn transfer(
-        ctx: Context<Transfer>,
+    pub fn transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, Transfer<'info>>,
         amount: u64,
     ) -> Result<()> {
+        // Token-2022 mints withhold fees on-chain and enforce trading rules in
+        // the transfer hook, so the transfer is forwarded as-is with the hook's
+        // extra accounts. Holder states created here are persisted first so the
+        // hook can load them, and reloaded afterwards to keep its updates.
+        if ctx.accounts.token_program.key() == Token2022::id() {
+            ctx.accounts.holder_state.exit(&crate::ID)?;
+            ctx.accounts.to_holder_state.exit(&crate::ID)?;
+            transfer_checked_with_hook(
+                &ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.from.to_account_info(),
+                &ctx.accounts.mint.to_account_info(),
+                &ctx.accounts.to.to_account_info(),
+                &ctx.accounts.authority.to_account_info(),
+                ctx.remaining_accounts,
+                amount,
+                ctx.accounts.mint.decimals,
+                &[],
+            )?;
+            ctx.accounts.holder_state.reload()?;
+            return ctx.accounts.to_holder_state.reload();
+        }
+
         let config = &ctx.accounts.config;
         let clock = Clock::get()?;
 
-        // Check trading enabled
-        require!(config.trading_enabled, BigBallsError::TradingNotEnabled);
+        // Classify the transfer against registered AMM pools
+        let direction = config.transfer_direction(&ctx.accounts.from.key(), &ctx.accounts.to.key());
+
+        // Check trading enabled for this direction
+        require!(!config.is_action_paused(direction.pause_flag()), BigBallsError::ActionPaused);
+        require!(!config.is_paused(clock.unix_timestamp), BigBallsError::TradingPaused);
+        require!(!config.emergency_shutdown, BigBallsError::EmergencyShutdown);
+
+        // Blocked wallets can neither send nor receive
+        require!(!is_blocked(&ctx.accounts.from_blocklist)?, BigBallsError::AddressBlocked);
+        require!(!is_blocked(&ctx.accounts.to_blocklist)?, BigBallsError::AddressBlocked);
+
+        // Launch or post-launch limits, depending on time since trading was enabled
+        let limits = config.active_limits(clock.unix_timestamp);
+
+        // Per-address exemptions; a missing exemption account exempts nothing.
+        // Trading rules follow the trader (the buyer on buys, the sender
+        // otherwise), so sending to an exempt address exempts nothing.
+        let from_exempt = ctx.accounts.from_exemption.as_ref().map(|e| e.flags()).unwrap_or_default();
+        let to_exempt = ctx.accounts.to_exemption.as_ref().map(|e| e.flags()).unwrap_or_default();
+        let trader_exempt = match direction {
+            TransferDirection::Buy => to_exempt,
+            _ => from_exempt,
+        };
 
         // Check transfer limits
-        require!(
-            amount <= config.max_transaction_amount,
-            BigBallsError::ExceedsMaxTransaction
-        );
+        if !trader_exempt.limit_exempt {
+            require!(
+                amount <= limits.max_transaction_amount,
+                BigBallsError::ExceedsMaxTransaction
+            );
+        }
 
-        // Check cooldown periods
+        // Check cooldown periods for the matching direction (buys are tracked on the buyer)
         let holder_state = &mut ctx.accounts.holder_state;
-        require!(
-            clock.unix_timestamp >= holder_state.last_transaction + config.transaction_cooldown,
-            BigBallsError::CooldownNotElapsed
-        );
+        let last_activity = match direction {
+            TransferDirection::Buy => ctx.accounts.to_holder_state.last_buy,
+            TransferDirection::Sell => holder_state.last_sell,
+            TransferDirection::WalletToWallet => holder_state.last_transaction,
+        };
+        if !trader_exempt.cooldown_exempt {
+            require!(
+                clock.unix_timestamp >= last_activity + config.cooldown_for(direction, &limits),
+                BigBallsError::CooldownNotElapsed
+            );
+        }
 
-        // Calculate fees
-        let total_fee_bp = config.reflection_fee_bp + config.marketing_fee_bp + 
-                          config.burn_fee_bp + config.dev_fee_bp;
-        let total_fee = amount * total_fee_bp as u64 / 10000;
+        // At most one transfer per slot for the wallet initiating the trade;
+        // cooldown exemptions don't lift this
+        let last_transfer_slot = match direction {
+            TransferDirection::Buy => ctx.accounts.to_holder_state.last_transfer_slot,
+            _ => holder_state.last_transfer_slot,
+        };
+        require!(last_transfer_slot != clock.slot, BigBallsError::OneTransferPerSlot);
+
+        // Buys right after launch are penalised or blocked unless whitelisted,
+        // depending on the anti-sniper mode
+        let sniper_buy = direction == TransferDirection::Buy
+            && !trader_exempt.sniper_whitelisted
+            && config.in_anti_sniper_window(clock.slot);
+        if sniper_buy {
+            require!(!config.anti_sniper_block, BigBallsError::SniperBlocked);
+        }
+
+        // Check rolling 24h volume caps
+        match direction {
+            TransferDirection::Buy if !trader_exempt.limit_exempt => ctx.accounts.to_holder_state
+                .record_buy(amount, clock.unix_timestamp, config.daily_buy_limit)?,
+            TransferDirection::Sell if !trader_exempt.limit_exempt => holder_state
+                .record_sell(amount, clock.unix_timestamp, config.daily_sell_limit)?,
+            _ => {}
+        }
+
+        // Settle reflections on pre-transfer balances before they change
+        holder_state.settle_reflections(ctx.accounts.from.amount, config.reflection_per_token)?;
+        ctx.accounts.to_holder_state
+            .settle_reflections(ctx.accounts.to.amount, config.reflection_per_token)?;
+
+        // Calculate fees (none if the trader is fee-exempt)
+        let take_fee = !trader_exempt.fee_exempt;
+        let fee_share = |fee_bp: u16| if take_fee { (amount as u128 * fee_bp as u128 / 10000) as u64 } else { 0 };
+
+        // The anti-sniper penalty is burned on top of the regular fees
+        let sniper_penalty = if sniper_buy {
+            (amount as u128 * config.anti_sniper_fee_bp as u128 / 10000) as u64
+        } else {
+            0
+        };
+        let total_fee = fee_share(config.total_fee_bp()) + sniper_penalty;
         
-        let reflection_amount = amount * config.reflection_fee_bp as u64 / 10000;
-        let marketing_amount = amount * config.marketing_fee_bp as u64 / 10000;
-        let burn_amount = amount * config.burn_fee_bp as u64 / 10000;
-        let dev_amount = amount * config.dev_fee_bp as u64 / 10000;
+        let reflection_amount = fee_share(config.reflection_fee_bp);
+        let marketing_amount = fee_share(config.marketing_fee_bp);
+        let burn_amount = fee_share(config.burn_fee_bp) + sniper_penalty;
+        let dev_amount = fee_share(config.dev_fee_bp);
+        if sniper_penalty > 0 {
+            emit!(SniperPenaltyApplied {
+                buyer: ctx.accounts.to.key(),
+                penalty: sniper_penalty,
+                slot: clock.slot,
+            });
+        }
+
+        // Check the recipient stays under the wallet cap; this one rule follows
+        // the recipient, since it is the recipient's balance being capped
+        if !to_exempt.limit_exempt && !config.is_max_wallet_exempt(&ctx.accounts.to.key()) {
+            let new_balance = ctx.accounts.to.amount
+                .checked_add(amount - total_fee)
+                .ok_or(BigBallsError::InvalidAmount)?;
+            require!(
+                new_balance <= limits.max_wallet_amount,
+                BigBallsError::ExceedsMaxWallet
+            );
+        }
 
         // Transfer main amount minus fees
         token::transfer(
@@ -121,7 +395,36 @@ pub mod bigballs_token {
             amount - total_fee,
         )?;
 
+        // Burn the burn share so supply actually shrinks
+        if burn_amount > 0 {
+            token::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Burn {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        from: ctx.accounts.from.to_account_info(),
+                        authority: ctx.accounts.authority.to_account_info(),
+                    },
+                ),
+                burn_amount,
+            )?;
+        }
+
         // Transfer fees
+        if reflection_amount > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.from.to_account_info(),
+                        to: ctx.accounts.reflection_pool.to_account_info(),
+                        authority: ctx.accounts.authority.to_account_info(),
+                    },
+                ),
+                reflection_amount,
+            )?;
+        }
+
         if marketing_amount > 0 {
             token::transfer(
                 CpiContext::new(
@@ -136,16 +439,51 @@ pub mod bigballs_token {
             )?;
         }
 
+        if dev_amount > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.from.to_account_info(),
+                        to: ctx.accounts.dev_wallet.to_account_info(),
+                        authority: ctx.accounts.authority.to_account_info(),
+                    },
+                ),
+                dev_amount,
+            )?;
+        }
+
         // Update holder state
         holder_state.last_transaction = clock.unix_timestamp;
         holder_state.total_transactions += 1;
         holder_state.total_amount += amount;
+        match direction {
+            TransferDirection::Buy => {
+                ctx.accounts.to_holder_state.last_buy = clock.unix_timestamp;
+                ctx.accounts.to_holder_state.last_transfer_slot = clock.slot;
+            }
+            TransferDirection::Sell => {
+                holder_state.last_sell = clock.unix_timestamp;
+                holder_state.last_transfer_slot = clock.slot;
+            }
+            TransferDirection::WalletToWallet => holder_state.last_transfer_slot = clock.slot,
+        }
+
+        // Checkpoint post-transfer balances so received tokens earn from here on
+        holder_state.checkpoint_balance = ctx.accounts.from.amount - amount;
+        ctx.accounts.to_holder_state.checkpoint_balance = ctx.accounts.to.amount + (amount - total_fee);
+
+        // Track cumulative burned supply and spread the reflection share
+        let config = &mut ctx.accounts.config;
+        config.record_burn(burn_amount, ctx.accounts.from.key(), clock.unix_timestamp)?;
+        config.add_reflections(reflection_amount)?;
 
         // Emit events
         emit!(TransferExecuted {
             from: ctx.accounts.from.key(),
             to: ctx.accounts.to.key(),
             amount: amount - total_fee,
+            direction,
             timestamp: clock.unix_timestamp,
         });
 
@@ -160,123 +498,3206 @@ pub mod bigballs_token {
         Ok(())
     }
 
-    pub fn set_trading_enabled(
-        ctx: Context<SetTrading>,
-        enabled: bool
+    /// Withdraw the transfer fees Token-2022 withheld on the mint and split
+    /// them like `transfer` splits its fees. Fees withheld on token accounts
+    /// must first be swept to the mint with Token-2022's permissionless
+    /// `HarvestWithheldTokensToMint`; anyone may then crank this.
+    pub fn distribute_withheld_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeWithheldFees<'info>>,
     ) -> Result<()> {
-        require!(
-            ctx.accounts.authority.key() == ctx.accounts.config.authority,
-            BigBallsError::Unauthorized
-        );
-        ctx.accounts.config.trading_enabled = enabled;
-        
-        emit!(TradingStatusChanged {
-            enabled,
+        let config_key = ctx.accounts.config.key();
+        let fee_bump = *ctx.bumps.get("fee_authority").unwrap();
+        let fee_seeds: &[&[&[u8]]] = &[&[b"fee_authority", config_key.as_ref(), &[fee_bump]]];
+
+        let pool_balance = ctx.accounts.reflection_pool.amount;
+        invoke_signed(
+            &transfer_fee::instruction::withdraw_withheld_tokens_from_mint(
+                ctx.accounts.token_program.key,
+                &ctx.accounts.mint.key(),
+                &ctx.accounts.reflection_pool.key(),
+                ctx.accounts.fee_authority.key,
+                &[],
+            )?,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.reflection_pool.to_account_info(),
+                ctx.accounts.fee_authority.to_account_info(),
+            ],
+            fee_seeds,
+        )?;
+        ctx.accounts.reflection_pool.reload()?;
+        let collected = ctx.accounts.reflection_pool.amount - pool_balance;
+        require!(collected > 0, BigBallsError::NoWithheldFees);
+
+        // Split by the configured shares; reflections absorb rounding and take
+        // everything if fees were withheld while the shares were all zero
+        let config = &ctx.accounts.config;
+        let total_fee_bp = config.total_fee_bp() as u128;
+        let fee_share = |fee_bp: u16| {
+            (collected as u128 * fee_bp as u128).checked_div(total_fee_bp).unwrap_or(0) as u64
+        };
+        let marketing_amount = fee_share(config.marketing_fee_bp);
+        let burn_amount = fee_share(config.burn_fee_bp);
+        let dev_amount = fee_share(config.dev_fee_bp);
+        let reflection_amount = collected - marketing_amount - burn_amount - dev_amount;
+
+        let reflection_bump = *ctx.bumps.get("reflection_authority").unwrap();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"reflection_authority", config_key.as_ref(), &[reflection_bump]]];
+
+        for (recipient, amount) in [
+            (ctx.accounts.marketing_wallet.to_account_info(), marketing_amount),
+            (ctx.accounts.dev_wallet.to_account_info(), dev_amount),
+        ] {
+            if amount > 0 {
+                transfer_checked_with_hook(
+                    &ctx.accounts.token_program.to_account_info(),
+                    &ctx.accounts.reflection_pool.to_account_info(),
+                    &ctx.accounts.mint.to_account_info(),
+                    &recipient,
+                    &ctx.accounts.reflection_authority.to_account_info(),
+                    ctx.remaining_accounts,
+                    amount,
+                    ctx.accounts.mint.decimals,
+                    signer_seeds,
+                )?;
+            }
+        }
+
+        if burn_amount > 0 {
+            token_interface::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::Burn {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        from: ctx.accounts.reflection_pool.to_account_info(),
+                        authority: ctx.accounts.reflection_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                burn_amount,
+            )?;
+        }
+
+        let clock = Clock::get()?;
+        let pool_key = ctx.accounts.reflection_pool.key();
+        let config = &mut ctx.accounts.config;
+        config.record_burn(burn_amount, pool_key, clock.unix_timestamp)?;
+        config.add_reflections(reflection_amount)?;
+
+        emit!(FeesCollected {
+            reflection_amount,
+            marketing_amount,
+            burn_amount,
+            dev_amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+
+        let extra_account_metas = transfer_hook_extra_account_metas(&ctx.accounts.config.key())?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas,
+        )?;
+
+        emit!(ExtraAccountMetaListInitialized {
+            mint: ctx.accounts.mint.key(),
+            extra_account_meta_list: ctx.accounts.extra_account_meta_list.key(),
             timestamp: Clock::get()?.unix_timestamp,
         });
+
         Ok(())
     }
 
-    pub fn update_fees(
-        ctx: Context<UpdateFees>,
-        reflection_fee_bp: u16,
-        marketing_fee_bp: u16,
-        burn_fee_bp: u16,
-        dev_fee_bp: u16,
+    // Rewrites an existing list so mints initialized against an older layout
+    // pick up the accounts the current hook expects
+    pub fn update_extra_account_meta_list(
+        ctx: Context<UpdateExtraAccountMetaList>,
     ) -> Result<()> {
-        require!(
-            ctx.accounts.authority.key() == ctx.accounts.config.authority,
-            BigBallsError::Unauthorized
-        );
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
 
-        // Validate total fees don't exceed reasonable limit (e.g., 10%)
-        let total_fee_bp = reflection_fee_bp + marketing_fee_bp + burn_fee_bp + dev_fee_bp;
-        require!(total_fee_bp <= 1000, BigBallsError::InvalidFeeConfiguration);
+        // Resize to the current layout, topping up rent from the authority
+        let list = ctx.accounts.extra_account_meta_list.to_account_info();
+        let space = ExtraAccountMetaList::size_of(TRANSFER_HOOK_EXTRA_ACCOUNTS)?;
+        let rent_due = Rent::get()?.minimum_balance(space).saturating_sub(list.lamports());
+        if rent_due > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: list.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        list.realloc(space, false)?;
 
-        let config = &mut ctx.accounts.config;
-        config.reflection_fee_bp = reflection_fee_bp;
-        config.marketing_fee_bp = marketing_fee_bp;
-        config.burn_fee_bp = burn_fee_bp;
-        config.dev_fee_bp = dev_fee_bp;
+        let extra_account_metas = transfer_hook_extra_account_metas(&ctx.accounts.config.key())?;
+        let mut data = list.try_borrow_mut_data()?;
+        data.fill(0);
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &extra_account_metas)?;
 
-        emit!(FeesUpdated {
-            reflection_fee_bp,
-            marketing_fee_bp,
-            burn_fee_bp,
-            dev_fee_bp,
+        emit!(ExtraAccountMetaListUpdated {
+            mint: ctx.accounts.mint.key(),
+            extra_account_meta_list: ctx.accounts.extra_account_meta_list.key(),
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(init, payer = authority, space = 8 + TokenConfig::LEN)]
-    pub config: Account<'info, TokenConfig>,
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    /// Create the holder state the transfer hook reads and updates. The hook
+    /// cannot create accounts, so on Token-2022 every wallet must run this once
+    /// for its token account before sending or buying (pools included);
+    /// `transfer` creates both parties' states itself.
+    pub fn initialize_holder_state(
+        _ctx: Context<InitializeHolderState>,
+    ) -> Result<()> {
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct Transfer<'info> {
-    #[account(mut)]
-    pub config: Account<'info, TokenConfig>,
-    #[account(mut)]
-    pub from: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub to: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub marketing_wallet: Account<'info, TokenAccount>,
-    #[account(
-        init_if_needed,
-        payer = authority,
-        space = 8 + HolderState::LEN,
-        seeds = [b"holder_state", from.key().as_ref()],
-        bump
-    )]
-    pub holder_state: Account<'info, HolderState>,
-    pub authority: Signer<'info>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    pub fn execute(
+        ctx: Context<Execute>,
+        amount: u64,
+    ) -> Result<()> {
+        // Only Token-2022 may drive the hook, mid-transfer
+        assert_is_transferring(&ctx.accounts.source)?;
 
-#[derive(Accounts)]
-pub struct SetTrading<'info> {
-    #[account(mut)]
-    pub config: Account<'info, TokenConfig>,
-    pub authority: Signer<'info>,
-}
+        // Token-2022 has already moved the tokens; settle reflections on both
+        // sides' pre-transfer balances so every balance change is checkpointed.
+        // The recipient's pre-transfer balance excludes what arrived net of
+        // the withheld transfer fee.
+        let config = &ctx.accounts.config;
+        let mut destination_state = if ctx.accounts.destination.key() == ctx.accounts.source.key() {
+            None
+        } else {
+            load_holder_state(&ctx.accounts.destination_holder_state)?
+        };
+        let destination_balance = token_2022_amount(&ctx.accounts.destination)?;
+        if let Some(destination_state) = destination_state.as_mut() {
+            let received = amount - withheld_transfer_fee(&ctx.accounts.mint, amount)?;
+            destination_state.settle_reflections(destination_balance - received, config.reflection_per_token)?;
+            destination_state.checkpoint_balance = destination_balance;
+        }
 
-#[derive(Accounts)]
-pub struct UpdateFees<'info> {
-    #[account(mut)]
-    pub config: Account<'info, TokenConfig>,
-    pub authority: Signer<'info>,
-}
+        // Program vaults (reflection pool, vesting, locks, votes, marketing,
+        // treasury) can only move tokens through this program's own checks
+        if ctx.accounts.owner.owner == &crate::ID {
+            return exit_holder_states(None, destination_state);
+        }
 
-#[account]
-pub struct TokenConfig {
-    pub name: String,
-    pub symbol: String,
-    pub decimals: u8,
-    pub total_supply: u64,
-    pub trading_enabled: bool,
-    pub authority: Pubkey,
-    pub marketing_wallet: Pubkey,
-    
-    // Trading limits
-    pub max_transaction_amount: u64,
-    pub max_wallet_amount: u64,
-    
-    // Fee configuration (in basis points)
+        // Senders must have run initialize_holder_state; the hook can't create it
+        let mut holder_state = load_holder_state(&ctx.accounts.holder_state)?
+            .ok_or(BigBallsError::HolderStateRequired)?;
+        let source_balance = token_2022_amount(&ctx.accounts.source)?;
+        holder_state.settle_reflections(source_balance + amount, config.reflection_per_token)?;
+        holder_state.checkpoint_balance = source_balance;
+
+        let clock = Clock::get()?;
+
+        // Blocked wallets can neither send nor receive; the recipient's entry
+        // is keyed by the destination's owner wallet, as in `transfer`
+        require!(!is_blocked(&ctx.accounts.owner_blocklist)?, BigBallsError::AddressBlocked);
+        require!(!is_blocked(&ctx.accounts.destination_blocklist)?, BigBallsError::AddressBlocked);
+
+        // Check trading enabled for this direction
+        let direction = config.transfer_direction(&ctx.accounts.source.key(), &ctx.accounts.destination.key());
+        require!(!config.is_action_paused(direction.pause_flag()), BigBallsError::ActionPaused);
+        require!(!config.is_paused(clock.unix_timestamp), BigBallsError::TradingPaused);
+        require!(!config.emergency_shutdown, BigBallsError::EmergencyShutdown);
+
+        // Launch or post-launch limits, depending on time since trading was enabled
+        let limits = config.active_limits(clock.unix_timestamp);
+
+        // Exemptions follow the trader as in `transfer`: the buyer on buys,
+        // the sending owner otherwise. Token-2022 withholds the transfer fee
+        // itself, so only limit, cooldown and sniper exemptions apply here.
+        let destination_exempt = exemption_flags(&ctx.accounts.destination_exemption)?;
+        let trader_exempt = match direction {
+            TransferDirection::Buy => destination_exempt,
+            _ => exemption_flags(&ctx.accounts.owner_exemption)?,
+        };
+
+        // Check transfer limits
+        if !trader_exempt.limit_exempt {
+            require!(
+                amount <= limits.max_transaction_amount,
+                BigBallsError::ExceedsMaxTransaction
+            );
+        }
+
+        // Rate limits follow the trader: buys are sent by the pool, so they are
+        // tracked on the buyer's state, which must exist
+        let trader_state = match direction {
+            TransferDirection::Buy => destination_state
+                .as_mut()
+                .ok_or(BigBallsError::HolderStateRequired)?,
+            _ => &mut holder_state,
+        };
+        let last_activity = match direction {
+            TransferDirection::Buy => trader_state.last_buy,
+            TransferDirection::Sell => trader_state.last_sell,
+            TransferDirection::WalletToWallet => trader_state.last_transaction,
+        };
+        if !trader_exempt.cooldown_exempt {
+            require!(
+                clock.unix_timestamp >= last_activity + config.cooldown_for(direction, &limits),
+                BigBallsError::CooldownNotElapsed
+            );
+        }
+        // Cooldown exemptions don't lift the one-transfer-per-slot limit
+        require!(trader_state.last_transfer_slot != clock.slot, BigBallsError::OneTransferPerSlot);
+
+        // The hook runs after the tokens moved and can't charge the sniper
+        // penalty, so buys in the window are rejected in either mode unless
+        // the buyer's wallet is whitelisted
+        if direction == TransferDirection::Buy && config.in_anti_sniper_window(clock.slot) {
+            require!(trader_exempt.sniper_whitelisted, BigBallsError::SniperBlocked);
+        }
+
+        // Check rolling 24h volume caps
+        match direction {
+            TransferDirection::Buy if !trader_exempt.limit_exempt => trader_state
+                .record_buy(amount, clock.unix_timestamp, config.daily_buy_limit)?,
+            TransferDirection::Sell if !trader_exempt.limit_exempt => trader_state
+                .record_sell(amount, clock.unix_timestamp, config.daily_sell_limit)?,
+            _ => {}
+        }
+        trader_state.last_transfer_slot = clock.slot;
+        match direction {
+            TransferDirection::Buy => trader_state.last_buy = clock.unix_timestamp,
+            TransferDirection::Sell => trader_state.last_sell = clock.unix_timestamp,
+            TransferDirection::WalletToWallet => {}
+        }
+
+        // Check the recipient stays under the wallet cap, on its post-transfer balance
+        let destination = ctx.accounts.destination.key();
+        if !destination_exempt.limit_exempt
+            && !config.is_max_wallet_exempt(&destination)
+            && !config.is_pooled_vault(&config.key(), &destination)
+        {
+            require!(
+                destination_balance <= limits.max_wallet_amount,
+                BigBallsError::ExceedsMaxWallet
+            );
+        }
+
+        // Update holder state
+        holder_state.last_transaction = clock.unix_timestamp;
+        holder_state.total_transactions += 1;
+        holder_state.total_amount += amount;
+        exit_holder_states(Some(holder_state), destination_state)?;
+
+        emit!(TransferExecuted {
+            from: ctx.accounts.source.key(),
+            to: ctx.accounts.destination.key(),
+            amount,
+            direction,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn mint_initial_supply(
+        ctx: Context<MintInitialSupply>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        require!(
+            !ctx.accounts.config.initial_supply_minted,
+            BigBallsError::InitialSupplyAlreadyMinted
+        );
+
+        let config_key = ctx.accounts.config.key();
+        let bump = *ctx.bumps.get("mint_authority").unwrap();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", config_key.as_ref(), &[bump]]];
+        let amount = ctx.accounts.config.total_supply;
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        ctx.accounts.config.initial_supply_minted = true;
+
+        emit!(InitialSupplyMinted {
+            mint: ctx.accounts.mint.key(),
+            destination: ctx.accounts.destination.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn distribute_initial_supply<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeInitialSupply<'info>>,
+        allocations: Vec<Allocation>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        require!(
+            !ctx.accounts.config.initial_supply_minted,
+            BigBallsError::InitialSupplyAlreadyMinted
+        );
+
+        // Validate buckets: bounded, cover exactly 100%, one per destination
+        require!(
+            !allocations.is_empty() && allocations.len() <= MAX_ALLOCATION_BUCKETS,
+            BigBallsError::InvalidAllocation
+        );
+        require!(
+            allocations.iter().map(|a| a.basis_points as u64).sum::<u64>() == 10000,
+            BigBallsError::InvalidAllocation
+        );
+        for (i, allocation) in allocations.iter().enumerate() {
+            require!(
+                !allocations[..i].iter().any(|a| a.destination == allocation.destination),
+                BigBallsError::InvalidAllocation
+            );
+        }
+        require!(
+            ctx.remaining_accounts.len() == allocations.len(),
+            BigBallsError::InvalidAllocation
+        );
+
+        let config_key = ctx.accounts.config.key();
+        let bump = *ctx.bumps.get("mint_authority").unwrap();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", config_key.as_ref(), &[bump]]];
+        let total_supply = ctx.accounts.config.total_supply;
+        let clock = Clock::get()?;
+
+        let mut minted = 0u64;
+        for (i, (allocation, destination)) in allocations.iter().zip(ctx.remaining_accounts).enumerate() {
+            require_keys_eq!(destination.key(), allocation.destination, BigBallsError::InvalidAllocation);
+            let destination_account =
+                InterfaceAccount::<token_interface::TokenAccount>::try_from(destination)?;
+            require_keys_eq!(destination_account.mint, ctx.accounts.mint.key(), BigBallsError::InvalidAllocation);
+
+            // The last bucket absorbs rounding so exactly total_supply is minted
+            let amount = if i == allocations.len() - 1 {
+                total_supply - minted
+            } else {
+                (total_supply as u128 * allocation.basis_points as u128 / 10000) as u64
+            };
+            minted += amount;
+
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: destination.clone(),
+                        authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+
+            emit!(AllocationDistributed {
+                mint: ctx.accounts.mint.key(),
+                destination: allocation.destination,
+                basis_points: allocation.basis_points,
+                amount,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        ctx.accounts.config.initial_supply_minted = true;
+
+        Ok(())
+    }
+
+    pub fn revoke_mint_authority(
+        ctx: Context<RevokeMintAuthority>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        require!(
+            ctx.accounts.config.initial_supply_minted,
+            BigBallsError::InitialSupplyNotMinted
+        );
+
+        let config_key = ctx.accounts.config.key();
+        let bump = *ctx.bumps.get("mint_authority").unwrap();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", config_key.as_ref(), &[bump]]];
+
+        // Permanently fix the supply; there is no way to restore a mint authority
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::SetAuthority {
+                    current_authority: ctx.accounts.mint_authority.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            token_interface::spl_token_2022::instruction::AuthorityType::MintTokens,
+            None,
+        )?;
+
+        ctx.accounts.config.mint_authority_revoked = true;
+
+        emit!(MintAuthorityRevoked {
+            mint: ctx.accounts.mint.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_trading_enabled(
+        ctx: Context<SetTrading>,
+        enabled: bool
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        let clock = Clock::get()?;
+        ctx.accounts.config.apply_trading_enabled(enabled, clock.unix_timestamp, clock.slot)
+    }
+
+    pub fn set_pause_flags(
+        ctx: Context<SetTrading>,
+        flags: u8,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        let clock = Clock::get()?;
+        ctx.accounts.config.apply_pause_flags(flags, clock.unix_timestamp, clock.slot)
+    }
+
+    pub fn update_fees(
+        ctx: Context<UpdateFees>,
+        reflection_fee_bp: u16,
+        marketing_fee_bp: u16,
+        burn_fee_bp: u16,
+        dev_fee_bp: u16,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        ctx.accounts.config.apply_fees(
+            reflection_fee_bp,
+            marketing_fee_bp,
+            burn_fee_bp,
+            dev_fee_bp,
+            Clock::get()?.unix_timestamp,
+        )?;
+        sync_transfer_fee(
+            &ctx.accounts.config,
+            ctx.accounts.mint.as_ref(),
+            ctx.accounts.fee_authority.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            ctx.bumps.get("fee_authority").copied(),
+        )
+    }
+
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        ctx.accounts.config.apply_pending_authority(new_authority, Clock::get()?.unix_timestamp)
+    }
+
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>,
+    ) -> Result<()> {
+        ctx.accounts.config.accept_pending_authority(
+            &ctx.accounts.new_authority.key(),
+            Clock::get()?.unix_timestamp,
+        )
+    }
+
+    pub fn renounce_authority(
+        ctx: Context<RenounceAuthority>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+
+        // Ownerless from here on: every admin instruction fails require_authority
+        let config = &mut ctx.accounts.config;
+        let old_authority = config.authority;
+        config.authority = Pubkey::default();
+        config.pending_authority = Pubkey::default();
+
+        emit!(AuthorityRenounced {
+            old_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn update_limits(
+        ctx: Context<UpdateLimits>,
+        max_transaction_amount: u64,
+        max_wallet_amount: u64,
+        buy_cooldown: i64,
+        sell_cooldown: i64,
+        transaction_cooldown: i64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        ctx.accounts.config.apply_limits(
+            max_transaction_amount,
+            max_wallet_amount,
+            buy_cooldown,
+            sell_cooldown,
+            transaction_cooldown,
+            Clock::get()?.unix_timestamp,
+        )
+    }
+
+    pub fn update_launch_limits(
+        ctx: Context<UpdateLimits>,
+        launch_phase_duration: i64,
+        launch_max_transaction_amount: u64,
+        launch_max_wallet_amount: u64,
+        launch_transaction_cooldown: i64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        ctx.accounts.config.apply_launch_limits(
+            launch_phase_duration,
+            launch_max_transaction_amount,
+            launch_max_wallet_amount,
+            launch_transaction_cooldown,
+            Clock::get()?.unix_timestamp,
+        )
+    }
+
+    pub fn update_anti_sniper(
+        ctx: Context<UpdateLimits>,
+        anti_sniper_slots: u64,
+        anti_sniper_fee_bp: u16,
+        anti_sniper_block: bool,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        ctx.accounts.config.apply_anti_sniper(
+            anti_sniper_slots,
+            anti_sniper_fee_bp,
+            anti_sniper_block,
+            Clock::get()?.unix_timestamp,
+        )
+    }
+
+    pub fn set_dev_wallet(
+        ctx: Context<SetDevWallet>,
+        dev_wallet: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        ctx.accounts.config.apply_dev_wallet(dev_wallet, Clock::get()?.unix_timestamp)
+    }
+
+    pub fn add_amm_pool(
+        ctx: Context<UpdateAmmPools>,
+        pool: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        ctx.accounts.config.apply_add_amm_pool(pool, Clock::get()?.unix_timestamp)
+    }
+
+    pub fn remove_amm_pool(
+        ctx: Context<UpdateAmmPools>,
+        pool: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        ctx.accounts.config.apply_remove_amm_pool(pool, Clock::get()?.unix_timestamp)
+    }
+
+    pub fn set_exemption(
+        ctx: Context<SetExemption>,
+        owner: Pubkey,
+        fee_exempt: bool,
+        limit_exempt: bool,
+        cooldown_exempt: bool,
+        sniper_whitelisted: bool,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        ctx.accounts.exemption.apply(
+            owner,
+            ExemptionFlags { fee_exempt, limit_exempt, cooldown_exempt, sniper_whitelisted },
+            Clock::get()?.unix_timestamp,
+        );
+        Ok(())
+    }
+
+    pub fn remove_exemption(
+        ctx: Context<RemoveExemption>,
+        owner: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+
+        emit!(ExemptionUpdated {
+            owner,
+            fee_exempt: false,
+            limit_exempt: false,
+            cooldown_exempt: false,
+            sniper_whitelisted: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // Creates an empty entry so governance can later set the owner's exemptions
+    pub fn initialize_exemption(
+        ctx: Context<InitializeExemption>,
+        owner: Pubkey,
+    ) -> Result<()> {
+        let exemption = &mut ctx.accounts.exemption;
+        exemption.owner = owner;
+        exemption.fee_exempt = false;
+        exemption.limit_exempt = false;
+        exemption.cooldown_exempt = false;
+        exemption.sniper_whitelisted = false;
+        Ok(())
+    }
+
+    pub fn initialize_reflection_pool(
+        ctx: Context<InitializeReflectionPool>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+
+        emit!(ReflectionPoolInitialized {
+            reflection_pool: ctx.accounts.reflection_pool.key(),
+            pool_authority: ctx.accounts.reflection_authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn claim_reflections<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimReflections<'info>>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let clock = Clock::get()?;
+        require!(!config.is_action_paused(PAUSE_REFLECTION_CLAIMS), BigBallsError::ActionPaused);
+
+        // Bring the holder's checkpoint up to date with the global accumulator
+        let holder_state = &mut ctx.accounts.holder_state;
+        holder_state.settle_reflections(
+            ctx.accounts.holder_token_account.amount,
+            config.reflection_per_token,
+        )?;
+
+        // Never pay out more than the pool actually holds. A zero claim still
+        // succeeds so a holder first seen here persists its checkpoint.
+        let amount = holder_state.pending_reflections.min(ctx.accounts.reflection_pool.amount);
+        if amount > 0 {
+            let config_key = config.key();
+            let bump = *ctx.bumps.get("reflection_authority").unwrap();
+            let signer_seeds: &[&[&[u8]]] = &[&[b"reflection_authority", config_key.as_ref(), &[bump]]];
+
+            transfer_checked_with_hook(
+                &ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.reflection_pool.to_account_info(),
+                &ctx.accounts.mint.to_account_info(),
+                &ctx.accounts.holder_token_account.to_account_info(),
+                &ctx.accounts.reflection_authority.to_account_info(),
+                ctx.remaining_accounts,
+                amount,
+                ctx.accounts.mint.decimals,
+                signer_seeds,
+            )?;
+            ctx.accounts.holder_token_account.reload()?;
+        }
+
+        // Checkpoint what actually arrived (Token-2022 withholds its fee)
+        let holder_state = &mut ctx.accounts.holder_state;
+        holder_state.pending_reflections -= amount;
+        holder_state.checkpoint_balance = ctx.accounts.holder_token_account.amount;
+        holder_state.last_reflection_claim = clock.unix_timestamp;
+
+        emit!(ReflectionsClaimed {
+            holder: ctx.accounts.holder_token_account.key(),
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn create_vesting<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateVesting<'info>>,
+        beneficiary: Pubkey,
+        total_amount: u64,
+        cliff_duration: i64,
+        vesting_duration: i64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        require!(total_amount > 0, BigBallsError::InvalidAmount);
+        require!(
+            vesting_duration > 0 && cliff_duration >= 0 && cliff_duration <= vesting_duration,
+            BigBallsError::InvalidVestingSchedule
+        );
+
+        let clock = Clock::get()?;
+
+        // Escrow the full grant up front so releases never depend on the
+        // authority, and vest what actually arrived in the vault
+        transfer_checked_with_hook(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.source.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            ctx.remaining_accounts,
+            total_amount,
+            ctx.accounts.mint.decimals,
+            &[],
+        )?;
+        ctx.accounts.vault.reload()?;
+        let total_amount = ctx.accounts.vault.amount;
+
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.config = ctx.accounts.config.key();
+        schedule.beneficiary = beneficiary;
+        schedule.vault = ctx.accounts.vault.key();
+        schedule.total_amount = total_amount;
+        schedule.released_amount = 0;
+        schedule.start_time = clock.unix_timestamp;
+        schedule.cliff_duration = cliff_duration;
+        schedule.vesting_duration = vesting_duration;
+
+        emit!(VestingCreated {
+            vesting_schedule: schedule.key(),
+            beneficiary,
+            total_amount,
+            start_time: schedule.start_time,
+            cliff_duration,
+            vesting_duration,
+        });
+
+        Ok(())
+    }
+
+    pub fn release_vested<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReleaseVested<'info>>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let amount = ctx.accounts.vesting_schedule.releasable_amount(clock.unix_timestamp);
+        require!(amount > 0, BigBallsError::NothingToRelease);
+
+        let config_key = ctx.accounts.config.key();
+        let bump = *ctx.bumps.get("vesting_authority").unwrap();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"vesting_authority", config_key.as_ref(), &[bump]]];
+
+        transfer_checked_with_hook(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.beneficiary_token_account.to_account_info(),
+            &ctx.accounts.vesting_authority.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.mint.decimals,
+            signer_seeds,
+        )?;
+
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.released_amount += amount;
+
+        emit!(VestedTokensReleased {
+            vesting_schedule: schedule.key(),
+            beneficiary: schedule.beneficiary,
+            amount,
+            total_released: schedule.released_amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn lock<'info>(
+        ctx: Context<'_, '_, '_, 'info, Lock<'info>>,
+        lock_id: u64,
+        amount: u64,
+        unlock_time: i64,
+    ) -> Result<()> {
+        require!(amount > 0, BigBallsError::InvalidAmount);
+        let clock = Clock::get()?;
+        require!(unlock_time > clock.unix_timestamp, BigBallsError::InvalidUnlockTime);
+
+        // Record what actually arrived in case the mint withholds a transfer fee
+        transfer_checked_with_hook(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.source.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.mint.decimals,
+            &[],
+        )?;
+        ctx.accounts.vault.reload()?;
+        let amount = ctx.accounts.vault.amount;
+
+        let lock_vault = &mut ctx.accounts.lock_vault;
+        lock_vault.owner = ctx.accounts.owner.key();
+        lock_vault.mint = ctx.accounts.mint.key();
+        lock_vault.vault = ctx.accounts.vault.key();
+        lock_vault.lock_id = lock_id;
+        lock_vault.amount = amount;
+        lock_vault.locked_at = clock.unix_timestamp;
+        lock_vault.unlock_time = unlock_time;
+
+        emit!(TokensLocked {
+            lock_vault: lock_vault.key(),
+            owner: lock_vault.owner,
+            mint: lock_vault.mint,
+            amount,
+            unlock_time,
+        });
+
+        Ok(())
+    }
+
+    pub fn extend_lock(
+        ctx: Context<ExtendLock>,
+        unlock_time: i64,
+    ) -> Result<()> {
+        let lock_vault = &mut ctx.accounts.lock_vault;
+
+        // Locks can only be lengthened, never shortened
+        require!(unlock_time > lock_vault.unlock_time, BigBallsError::InvalidUnlockTime);
+
+        let old_unlock_time = lock_vault.unlock_time;
+        lock_vault.unlock_time = unlock_time;
+
+        emit!(LockExtended {
+            lock_vault: lock_vault.key(),
+            old_unlock_time,
+            new_unlock_time: unlock_time,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn unlock<'info>(
+        ctx: Context<'_, '_, '_, 'info, Unlock<'info>>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let lock_vault = &ctx.accounts.lock_vault;
+        require!(clock.unix_timestamp >= lock_vault.unlock_time, BigBallsError::LockNotExpired);
+
+        let amount = ctx.accounts.vault.amount;
+        let owner_key = lock_vault.owner;
+        let mint_key = lock_vault.mint;
+        let lock_id_bytes = lock_vault.lock_id.to_le_bytes();
+        let bump = *ctx.bumps.get("lock_vault").unwrap();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"lock_vault",
+            owner_key.as_ref(),
+            mint_key.as_ref(),
+            lock_id_bytes.as_ref(),
+            &[bump],
+        ]];
+
+        transfer_checked_with_hook(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.destination.to_account_info(),
+            &ctx.accounts.lock_vault.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.mint.decimals,
+            signer_seeds,
+        )?;
+
+        // Return the vault's rent to the owner; the lock record closes via `close`
+        harvest_withheld_fees(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.vault.to_account_info(),
+        )?;
+        token_interface::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::CloseAccount {
+                    account: ctx.accounts.vault.to_account_info(),
+                    destination: ctx.accounts.owner.to_account_info(),
+                    authority: ctx.accounts.lock_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+        )?;
+
+        emit!(TokensUnlocked {
+            lock_vault: ctx.accounts.lock_vault.key(),
+            owner: owner_key,
+            mint: mint_key,
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn initialize_marketing_vault(
+        ctx: Context<InitializeMarketingVault>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        validate_signer_set(&signers, threshold, MAX_MARKETING_SIGNERS)?;
+
+        let config = &mut ctx.accounts.config;
+        let old_marketing_wallet = config.marketing_wallet;
+
+        // Marketing fees now accrue to the vault and leave only via proposals
+        config.marketing_wallet = ctx.accounts.marketing_vault.key();
+
+        let multisig = &mut ctx.accounts.marketing_multisig;
+        multisig.config = config.key();
+        multisig.vault = ctx.accounts.marketing_vault.key();
+        multisig.signers = signers.clone();
+        multisig.threshold = threshold;
+        multisig.timelock_threshold = config.total_supply / 200; // 0.5% of supply
+        multisig.timelock_duration = MIN_MARKETING_TIMELOCK;
+        multisig.proposal_count = 0;
+
+        emit!(MarketingVaultInitialized {
+            marketing_vault: multisig.vault,
+            old_marketing_wallet,
+            signers,
+            threshold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Propose a spend from the marketing vault or a change to the multisig
+    /// itself. Timelock and signer changes always wait out the timelock, so
+    /// holders see them coming.
+    pub fn propose_marketing_action(
+        ctx: Context<ProposeMarketingAction>,
+        action: MarketingAction,
+    ) -> Result<()> {
+        let proposer = ctx.accounts.proposer.key();
+        let multisig = &mut ctx.accounts.marketing_multisig;
+        require!(multisig.is_signer(&proposer), BigBallsError::NotMultisigSigner);
+        action.validate(&ctx.accounts.config)?;
+
+        let clock = Clock::get()?;
+        let proposal = &mut ctx.accounts.marketing_proposal;
+        proposal.multisig = multisig.key();
+        proposal.index = multisig.proposal_count;
+        proposal.proposer = proposer;
+        proposal.action = action.clone();
+        proposal.approvals = Vec::new();
+        proposal.created_at = clock.unix_timestamp;
+        proposal.executable_at = 0;
+        proposal.executed = false;
+        multisig.proposal_count += 1;
+
+        emit!(MarketingProposalCreated {
+            marketing_proposal: proposal.key(),
+            index: proposal.index,
+            proposer,
+            action,
+            timestamp: clock.unix_timestamp,
+        });
+
+        // Proposing counts as the proposer's approval
+        proposal.approve(multisig, proposer, clock.unix_timestamp)
+    }
+
+    pub fn approve_marketing_proposal(
+        ctx: Context<ApproveMarketingProposal>,
+    ) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        let multisig = &ctx.accounts.marketing_multisig;
+        require!(multisig.is_signer(&signer), BigBallsError::NotMultisigSigner);
+
+        let proposal = &mut ctx.accounts.marketing_proposal;
+        require!(!proposal.executed, BigBallsError::SpendAlreadyExecuted);
+        proposal.approve(multisig, signer, Clock::get()?.unix_timestamp)
+    }
+
+    pub fn execute_marketing_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteMarketingProposal<'info>>,
+    ) -> Result<()> {
+        let executor = ctx.accounts.executor.key();
+        require!(
+            ctx.accounts.marketing_multisig.is_signer(&executor),
+            BigBallsError::NotMultisigSigner
+        );
+
+        // Approvals from rotated-out signers no longer count
+        let clock = Clock::get()?;
+        let proposal = &ctx.accounts.marketing_proposal;
+        require!(!proposal.executed, BigBallsError::SpendAlreadyExecuted);
+        require!(
+            proposal.valid_approvals(&ctx.accounts.marketing_multisig) >= ctx.accounts.marketing_multisig.threshold as usize,
+            BigBallsError::InsufficientApprovals
+        );
+        require!(
+            proposal.executable_at > 0 && clock.unix_timestamp >= proposal.executable_at,
+            BigBallsError::TimelockNotElapsed
+        );
+
+        match proposal.action.clone() {
+            MarketingAction::Spend { recipient, amount } => {
+                let recipient_account = ctx.accounts.recipient.as_ref()
+                    .filter(|account| account.key() == recipient)
+                    .ok_or(BigBallsError::InvalidRecipient)?;
+
+                let config_key = ctx.accounts.config.key();
+                let bump = *ctx.bumps.get("marketing_authority").unwrap();
+                let signer_seeds: &[&[&[u8]]] = &[&[b"marketing_authority", config_key.as_ref(), &[bump]]];
+
+                transfer_checked_with_hook(
+                    &ctx.accounts.token_program.to_account_info(),
+                    &ctx.accounts.marketing_vault.to_account_info(),
+                    &ctx.accounts.mint.to_account_info(),
+                    &recipient_account.to_account_info(),
+                    &ctx.accounts.marketing_authority.to_account_info(),
+                    ctx.remaining_accounts,
+                    amount,
+                    ctx.accounts.mint.decimals,
+                    signer_seeds,
+                )?;
+            }
+            MarketingAction::UpdateTimelock { timelock_threshold, timelock_duration } => {
+                let multisig = &mut ctx.accounts.marketing_multisig;
+                multisig.timelock_threshold = timelock_threshold;
+                multisig.timelock_duration = timelock_duration;
+
+                emit!(MarketingTimelockUpdated {
+                    timelock_threshold,
+                    timelock_duration,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            MarketingAction::RotateSigners { signers, threshold } => {
+                let multisig = &mut ctx.accounts.marketing_multisig;
+                multisig.signers = signers.clone();
+                multisig.threshold = threshold;
+
+                emit!(MarketingSignersRotated {
+                    signers,
+                    threshold,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+
+        let proposal = &mut ctx.accounts.marketing_proposal;
+        proposal.executed = true;
+
+        emit!(MarketingProposalExecuted {
+            marketing_proposal: proposal.key(),
+            index: proposal.index,
+            executor,
+            action: proposal.action.clone(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+
+        ctx.accounts.config.vote_vault_bump = Some(*ctx.bumps.get("vote_vault").unwrap());
+        let governance = &mut ctx.accounts.governance;
+        governance.init_defaults(&ctx.accounts.config, ctx.accounts.vote_vault.key());
+
+        emit!(GovernanceInitialized {
+            governance: governance.key(),
+            vote_vault: governance.vote_vault,
+            proposal_threshold: governance.proposal_threshold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        action: ProposalAction,
+        description_hash: [u8; 32],
+    ) -> Result<()> {
+        // Proposals that lift the governance pause stay open so it can be undone
+        require!(
+            !ctx.accounts.config.is_action_paused(PAUSE_GOVERNANCE) || action.lifts_governance_pause(),
+            BigBallsError::ActionPaused
+        );
+        // Escrowed votes back one open proposal at a time and stay locked
+        // until it closes, so the same tokens can't open unlimited proposals
+        let clock = Clock::get()?;
+        let governance = &mut ctx.accounts.governance;
+        let voter_record = &mut ctx.accounts.voter_record;
+        require!(
+            voter_record.voting_power >= governance.proposal_threshold,
+            BigBallsError::InsufficientProposalBalance
+        );
+        require!(
+            clock.unix_timestamp >= voter_record.active_proposal_ends_at,
+            BigBallsError::ProposalAlreadyActive
+        );
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.governance = governance.key();
+        proposal.index = governance.proposal_count;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.action = action;
+        proposal.description_hash = description_hash;
+        proposal.votes_for = 0;
+        proposal.votes_against = 0;
+        proposal.created_at = clock.unix_timestamp;
+        proposal.voting_ends_at = clock.unix_timestamp + governance.voting_period;
+        proposal.executable_at = 0;
+        proposal.status = ProposalStatus::Active;
+        governance.proposal_count += 1;
+        voter_record.active_proposal_ends_at = proposal.voting_ends_at;
+        voter_record.locked_until = voter_record.locked_until.max(proposal.voting_ends_at);
+
+        emit!(ProposalCreated {
+            proposal: proposal.key(),
+            index: proposal.index,
+            proposer: proposal.proposer,
+            action,
+            description_hash,
+            voting_ends_at: proposal.voting_ends_at,
+        });
+
+        Ok(())
+    }
+
+    pub fn cast_vote(
+        ctx: Context<CastVote>,
+        support: bool,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            !ctx.accounts.config.is_action_paused(PAUSE_GOVERNANCE) || proposal.action.lifts_governance_pause(),
+            BigBallsError::ActionPaused
+        );
+        require!(
+            proposal.status == ProposalStatus::Active && clock.unix_timestamp < proposal.voting_ends_at,
+            BigBallsError::VotingClosed
+        );
+
+        let voter_record = &mut ctx.accounts.voter_record;
+        let weight = voter_record.voting_power;
+        require!(weight > 0, BigBallsError::NoVotingPower);
+
+        if support {
+            proposal.votes_for += weight;
+        } else {
+            proposal.votes_against += weight;
+        }
+
+        // Escrowed tokens stay locked until every proposal voted on has closed
+        voter_record.locked_until = voter_record.locked_until.max(proposal.voting_ends_at);
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.weight = weight;
+        vote_record.support = support;
+
+        emit!(VoteCast {
+            proposal: proposal.key(),
+            voter: vote_record.voter,
+            support,
+            weight,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn finalize_proposal(
+        ctx: Context<FinalizeProposal>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let governance = &ctx.accounts.governance;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.status == ProposalStatus::Active, BigBallsError::ProposalNotActive);
+        require!(clock.unix_timestamp >= proposal.voting_ends_at, BigBallsError::VotingStillOpen);
+
+        let total_votes = proposal.votes_for as u128 + proposal.votes_against as u128;
+        let quorum = ctx.accounts.config.circulating_supply() as u128 * governance.quorum_bp as u128 / 10000;
+        let approved = total_votes >= quorum
+            && total_votes > 0
+            && proposal.votes_for as u128 * 10000 >= total_votes * governance.approval_bp as u128;
+
+        if approved {
+            proposal.status = ProposalStatus::Succeeded;
+            proposal.executable_at = clock.unix_timestamp + governance.execution_delay;
+        } else {
+            proposal.status = ProposalStatus::Defeated;
+        }
+
+        emit!(ProposalFinalized {
+            proposal: proposal.key(),
+            status: proposal.status,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            executable_at: proposal.executable_at,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn execute_proposal(
+        ctx: Context<ExecuteProposal>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let governance_key = ctx.accounts.governance.key();
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.status == ProposalStatus::Succeeded, BigBallsError::ProposalNotSucceeded);
+        require!(clock.unix_timestamp >= proposal.executable_at, BigBallsError::TimelockNotElapsed);
+
+        // The governance PDA acts as the config authority for executed actions
+        let config = &mut ctx.accounts.config;
+        match proposal.action {
+            ProposalAction::AcceptAuthority => {
+                config.accept_pending_authority(&governance_key, clock.unix_timestamp)?;
+            }
+            ProposalAction::UpdateFees { reflection_fee_bp, marketing_fee_bp, burn_fee_bp, dev_fee_bp } => {
+                config.require_authority(&governance_key)?;
+                config.apply_fees(
+                    reflection_fee_bp,
+                    marketing_fee_bp,
+                    burn_fee_bp,
+                    dev_fee_bp,
+                    clock.unix_timestamp,
+                )?;
+                sync_transfer_fee(
+                    config,
+                    ctx.accounts.mint.as_ref(),
+                    ctx.accounts.fee_authority.as_ref(),
+                    ctx.accounts.token_program.as_ref(),
+                    ctx.bumps.get("fee_authority").copied(),
+                )?;
+            }
+            ProposalAction::SetTradingEnabled { enabled } => {
+                config.require_authority(&governance_key)?;
+                config.apply_trading_enabled(enabled, clock.unix_timestamp, clock.slot)?;
+            }
+            ProposalAction::SetPauseFlags { flags } => {
+                config.require_authority(&governance_key)?;
+                config.apply_pause_flags(flags, clock.unix_timestamp, clock.slot)?;
+            }
+            ProposalAction::BlockAddress { address, reason } => {
+                config.require_authority(&governance_key)?;
+                let entry = ctx.accounts.blocklist_entry.as_mut().ok_or(BigBallsError::BlocklistEntryRequired)?;
+                require_keys_eq!(entry.address, address, BigBallsError::BlocklistEntryRequired);
+                entry.apply_block(address, reason, clock.unix_timestamp);
+            }
+            ProposalAction::UnblockAddress { address } => {
+                config.require_authority(&governance_key)?;
+                let entry = ctx.accounts.blocklist_entry.as_mut().ok_or(BigBallsError::BlocklistEntryRequired)?;
+                require_keys_eq!(entry.address, address, BigBallsError::BlocklistEntryRequired);
+                entry.apply_unblock(clock.unix_timestamp);
+            }
+            ProposalAction::ScheduleBurn { amount } => {
+                config.require_authority(&governance_key)?;
+                let treasury = ctx.accounts.treasury.as_mut().ok_or(BigBallsError::TreasuryRequired)?;
+                treasury.schedule_burn(amount, config, clock.unix_timestamp)?;
+            }
+            ProposalAction::CancelBurn => {
+                config.require_authority(&governance_key)?;
+                let treasury = ctx.accounts.treasury.as_mut().ok_or(BigBallsError::TreasuryRequired)?;
+                treasury.cancel_burn(clock.unix_timestamp)?;
+            }
+            ProposalAction::ProposeAuthority { new_authority } => {
+                config.require_authority(&governance_key)?;
+                config.apply_pending_authority(new_authority, clock.unix_timestamp)?;
+            }
+            ProposalAction::UpdateLimits {
+                max_transaction_amount,
+                max_wallet_amount,
+                buy_cooldown,
+                sell_cooldown,
+                transaction_cooldown,
+            } => {
+                config.require_authority(&governance_key)?;
+                config.apply_limits(
+                    max_transaction_amount,
+                    max_wallet_amount,
+                    buy_cooldown,
+                    sell_cooldown,
+                    transaction_cooldown,
+                    clock.unix_timestamp,
+                )?;
+            }
+            ProposalAction::UpdateLaunchLimits {
+                launch_phase_duration,
+                launch_max_transaction_amount,
+                launch_max_wallet_amount,
+                launch_transaction_cooldown,
+            } => {
+                config.require_authority(&governance_key)?;
+                config.apply_launch_limits(
+                    launch_phase_duration,
+                    launch_max_transaction_amount,
+                    launch_max_wallet_amount,
+                    launch_transaction_cooldown,
+                    clock.unix_timestamp,
+                )?;
+            }
+            ProposalAction::UpdateAntiSniper { anti_sniper_slots, anti_sniper_fee_bp, anti_sniper_block } => {
+                config.require_authority(&governance_key)?;
+                config.apply_anti_sniper(
+                    anti_sniper_slots,
+                    anti_sniper_fee_bp,
+                    anti_sniper_block,
+                    clock.unix_timestamp,
+                )?;
+            }
+            ProposalAction::SetDevWallet { dev_wallet } => {
+                config.require_authority(&governance_key)?;
+                config.apply_dev_wallet(dev_wallet, clock.unix_timestamp)?;
+            }
+            ProposalAction::AddAmmPool { pool } => {
+                config.require_authority(&governance_key)?;
+                config.apply_add_amm_pool(pool, clock.unix_timestamp)?;
+            }
+            ProposalAction::RemoveAmmPool { pool } => {
+                config.require_authority(&governance_key)?;
+                config.apply_remove_amm_pool(pool, clock.unix_timestamp)?;
+            }
+            ProposalAction::SetExemption { owner, fee_exempt, limit_exempt, cooldown_exempt, sniper_whitelisted } => {
+                config.require_authority(&governance_key)?;
+                let exemption = ctx.accounts.exemption.as_mut().ok_or(BigBallsError::ExemptionRequired)?;
+                require_keys_eq!(exemption.owner, owner, BigBallsError::ExemptionRequired);
+                exemption.apply(
+                    owner,
+                    ExemptionFlags { fee_exempt, limit_exempt, cooldown_exempt, sniper_whitelisted },
+                    clock.unix_timestamp,
+                );
+            }
+        }
+
+        proposal.status = ProposalStatus::Executed;
+
+        emit!(ProposalExecuted {
+            proposal: proposal.key(),
+            action: proposal.action,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_votes<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositVotes<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, BigBallsError::InvalidAmount);
+
+        // Settle first so the escrowed amount keeps earning reflections, and
+        // persist the state so a Token-2022 transfer hook can load it
+        let holder_state = &mut ctx.accounts.holder_state;
+        holder_state.settle_reflections(
+            ctx.accounts.voter_token_account.amount,
+            ctx.accounts.config.reflection_per_token,
+        )?;
+        holder_state.exit(&crate::ID)?;
+
+        let vault_balance = ctx.accounts.vote_vault.amount;
+        transfer_checked_with_hook(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.voter_token_account.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.vote_vault.to_account_info(),
+            &ctx.accounts.voter.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.mint.decimals,
+            &[],
+        )?;
+
+        // Voting power is what actually reached the vault
+        ctx.accounts.vote_vault.reload()?;
+        ctx.accounts.voter_token_account.reload()?;
+        let amount = ctx.accounts.vote_vault.amount - vault_balance;
+
+        let holder_state = &mut ctx.accounts.holder_state;
+        holder_state.reload()?;
+        holder_state.escrowed_amount += amount;
+        holder_state.checkpoint_balance = ctx.accounts.voter_token_account.amount;
+
+        let voter_record = &mut ctx.accounts.voter_record;
+        voter_record.voter = ctx.accounts.voter.key();
+        voter_record.token_account = ctx.accounts.voter_token_account.key();
+        voter_record.voting_power += amount;
+
+        emit!(VotesDeposited {
+            voter: voter_record.voter,
+            amount,
+            voting_power: voter_record.voting_power,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn withdraw_votes<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawVotes<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let voter_record = &ctx.accounts.voter_record;
+        require!(amount > 0 && amount <= voter_record.voting_power, BigBallsError::InvalidAmount);
+        require!(clock.unix_timestamp >= voter_record.locked_until, BigBallsError::VotesLocked);
+
+        let holder_state = &mut ctx.accounts.holder_state;
+        holder_state.settle_reflections(
+            ctx.accounts.voter_token_account.amount,
+            ctx.accounts.config.reflection_per_token,
+        )?;
+
+        let config_key = ctx.accounts.config.key();
+        let bump = *ctx.bumps.get("governance").unwrap();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"governance", config_key.as_ref(), &[bump]]];
+
+        transfer_checked_with_hook(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.vote_vault.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.voter_token_account.to_account_info(),
+            &ctx.accounts.governance.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.mint.decimals,
+            signer_seeds,
+        )?;
+        ctx.accounts.voter_token_account.reload()?;
+
+        let holder_state = &mut ctx.accounts.holder_state;
+        holder_state.escrowed_amount -= amount;
+        holder_state.checkpoint_balance = ctx.accounts.voter_token_account.amount;
+
+        let voter_record = &mut ctx.accounts.voter_record;
+        voter_record.voting_power -= amount;
+
+        emit!(VotesWithdrawn {
+            voter: voter_record.voter,
+            amount,
+            voting_power: voter_record.voting_power,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn initialize_treasury(
+        ctx: Context<InitializeTreasury>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+
+        ctx.accounts.config.treasury_vault_bump = Some(*ctx.bumps.get("treasury_vault").unwrap());
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.config = ctx.accounts.config.key();
+        treasury.vault = ctx.accounts.treasury_vault.key();
+        treasury.pending_burn_amount = 0;
+        treasury.burn_executable_at = 0;
+        treasury.burn_count = 0;
+
+        emit!(TreasuryInitialized {
+            treasury: treasury.key(),
+            treasury_vault: treasury.vault,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn execute_burn(
+        ctx: Context<ExecuteBurn>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let treasury = &ctx.accounts.treasury;
+        let amount = treasury.pending_burn_amount;
+        require!(amount > 0, BigBallsError::NoBurnScheduled);
+        require!(clock.unix_timestamp >= treasury.burn_executable_at, BigBallsError::TimelockNotElapsed);
+        require!(!treasury.is_burn_expired(clock.unix_timestamp), BigBallsError::BurnExpired);
+
+        let config_key = ctx.accounts.config.key();
+        let bump = *ctx.bumps.get("treasury").unwrap();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"treasury", config_key.as_ref(), &[bump]]];
+
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.treasury_vault.to_account_info(),
+                    authority: ctx.accounts.treasury.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        let config = &mut ctx.accounts.config;
+        config.total_burned = config.total_burned
+            .checked_add(amount)
+            .ok_or(BigBallsError::InvalidAmount)?;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.pending_burn_amount = 0;
+        treasury.burn_executable_at = 0;
+        treasury.burn_count += 1;
+
+        emit!(ManualBurnExecuted {
+            index: treasury.burn_count,
+            amount,
+            total_burned: config.total_burned,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn initialize_price_oracle(
+        ctx: Context<InitializePriceOracle>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+
+        let oracle = &mut ctx.accounts.price_oracle;
+        oracle.config = ctx.accounts.config.key();
+        oracle.pool_token_account = ctx.accounts.pool_token_account.key();
+        oracle.pool_quote_account = ctx.accounts.pool_quote_account.key();
+        oracle.drop_threshold_bp = 5000; // 50% drop
+        oracle.window = 3600;            // within 1 hour
+        oracle.pause_duration = MAX_PAUSE_DURATION;
+        oracle.observations = [PriceObservation::default(); PRICE_OBSERVATIONS];
+        oracle.next_index = 0;
+
+        emit!(PriceOracleInitialized {
+            pool_token_account: oracle.pool_token_account,
+            pool_quote_account: oracle.pool_quote_account,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn update_circuit_breaker(
+        ctx: Context<UpdateCircuitBreaker>,
+        drop_threshold_bp: u16,
+        window: i64,
+        pause_duration: i64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        require!(
+            drop_threshold_bp > 0 && drop_threshold_bp < 10000,
+            BigBallsError::InvalidCircuitBreakerConfiguration
+        );
+        // The window must fit a confirmed peak followed by a confirmed drop
+        require!(
+            window >= MIN_OBSERVATION_INTERVAL * 2 * CIRCUIT_BREAKER_CONFIRMATIONS as i64
+                && window <= MIN_OBSERVATION_INTERVAL * PRICE_OBSERVATIONS as i64,
+            BigBallsError::InvalidCircuitBreakerConfiguration
+        );
+        require!(
+            pause_duration > 0 && pause_duration <= MAX_PAUSE_DURATION,
+            BigBallsError::InvalidCircuitBreakerConfiguration
+        );
+
+        let oracle = &mut ctx.accounts.price_oracle;
+        oracle.drop_threshold_bp = drop_threshold_bp;
+        oracle.window = window;
+        oracle.pause_duration = pause_duration;
+
+        emit!(CircuitBreakerUpdated {
+            drop_threshold_bp,
+            window,
+            pause_duration,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn check_circuit_breaker(
+        ctx: Context<CheckCircuitBreaker>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let token_reserve = ctx.accounts.pool_token_account.amount;
+        require!(token_reserve > 0, BigBallsError::InvalidPoolReserves);
+        let price = ctx.accounts.pool_quote_account.amount as u128 * PRICE_PRECISION / token_reserve as u128;
+
+        let oracle = &mut ctx.accounts.price_oracle;
+        require!(
+            now >= oracle.last_observed_at() + MIN_OBSERVATION_INTERVAL,
+            BigBallsError::ObservationTooSoon
+        );
+
+        // Compare the price held over the latest observations against the
+        // highest price held over any run within the window
+        oracle.record(now, price);
+        let reference_price = oracle.peak_price_since(now - oracle.window);
+
+        let config = &mut ctx.accounts.config;
+        config.clear_expired_pause(now);
+        let Some(current_price) = oracle.recent_price() else {
+            return Ok(());
+        };
+        if oracle.is_drop(reference_price, current_price) && !config.is_paused(now) {
+            config.paused_until = now + oracle.pause_duration;
+            config.pause_reason = PAUSE_REASON_PRICE_DROP;
+
+            emit!(CircuitBreakerTriggered {
+                reason: PAUSE_REASON_PRICE_DROP,
+                reference_price,
+                current_price,
+                paused_until: config.paused_until,
+                timestamp: now,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn initialize_guardian_set(
+        ctx: Context<InitializeGuardianSet>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+        timelock_duration: i64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        validate_signer_set(&guardians, threshold, MAX_GUARDIANS)?;
+        require!(
+            guardians.len() >= MIN_GUARDIANS && threshold >= MIN_GUARDIAN_THRESHOLD,
+            BigBallsError::InvalidSignerSet
+        );
+        require!(timelock_duration >= MIN_GUARDIAN_TIMELOCK, BigBallsError::InvalidTimelock);
+
+        // Once created the set is independent of the config authority
+        let guardian_set = &mut ctx.accounts.guardian_set;
+        guardian_set.config = ctx.accounts.config.key();
+        guardian_set.guardians = guardians.clone();
+        guardian_set.threshold = threshold;
+        guardian_set.timelock_duration = timelock_duration;
+        guardian_set.action_count = 0;
+
+        emit!(GuardianSetInitialized {
+            guardian_set: guardian_set.key(),
+            guardians,
+            threshold,
+            timelock_duration,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn propose_emergency_action(
+        ctx: Context<ProposeEmergencyAction>,
+        kind: EmergencyActionKind,
+        reason_hash: [u8; 32],
+        reason_uri: String,
+    ) -> Result<()> {
+        require!(reason_uri.len() <= MAX_REASON_URI_LEN, BigBallsError::ReasonUriTooLong);
+
+        let proposer = ctx.accounts.guardian.key();
+        let guardian_set = &mut ctx.accounts.guardian_set;
+        require!(guardian_set.is_guardian(&proposer), BigBallsError::NotGuardian);
+
+        let clock = Clock::get()?;
+        let action = &mut ctx.accounts.emergency_action;
+        action.guardian_set = guardian_set.key();
+        action.index = guardian_set.action_count;
+        action.kind = kind;
+        action.reason_hash = reason_hash;
+        action.reason_uri = reason_uri.clone();
+        action.proposer = proposer;
+        action.approvals = Vec::new();
+        action.created_at = clock.unix_timestamp;
+        action.executable_at = 0;
+        action.executed = false;
+        guardian_set.action_count += 1;
+
+        emit!(EmergencyActionProposed {
+            emergency_action: action.key(),
+            index: action.index,
+            kind,
+            proposer,
+            reason_hash,
+            reason_uri,
+            timestamp: clock.unix_timestamp,
+        });
+
+        // Proposing counts as the proposer's approval
+        action.approve(guardian_set, proposer, clock.unix_timestamp)
+    }
+
+    pub fn approve_emergency_action(
+        ctx: Context<ApproveEmergencyAction>,
+    ) -> Result<()> {
+        let guardian = ctx.accounts.guardian.key();
+        let guardian_set = &ctx.accounts.guardian_set;
+        require!(guardian_set.is_guardian(&guardian), BigBallsError::NotGuardian);
+
+        let action = &mut ctx.accounts.emergency_action;
+        require!(!action.executed, BigBallsError::EmergencyActionAlreadyExecuted);
+        action.approve(guardian_set, guardian, Clock::get()?.unix_timestamp)
+    }
+
+    pub fn execute_emergency_action(
+        ctx: Context<ExecuteEmergencyAction>,
+    ) -> Result<()> {
+        let executor = ctx.accounts.guardian.key();
+        let guardian_set = &ctx.accounts.guardian_set;
+        require!(guardian_set.is_guardian(&executor), BigBallsError::NotGuardian);
+
+        let clock = Clock::get()?;
+        let action = &mut ctx.accounts.emergency_action;
+        require!(!action.executed, BigBallsError::EmergencyActionAlreadyExecuted);
+        require!(
+            action.approvals.len() >= guardian_set.threshold as usize,
+            BigBallsError::InsufficientApprovals
+        );
+        require!(clock.unix_timestamp >= action.executable_at, BigBallsError::TimelockNotElapsed);
+        require!(!action.is_expired(clock.unix_timestamp), BigBallsError::EmergencyActionExpired);
+
+        let config = &mut ctx.accounts.config;
+        config.emergency_shutdown = action.kind == EmergencyActionKind::Shutdown;
+        action.executed = true;
+
+        emit!(EmergencyActionExecuted {
+            emergency_action: action.key(),
+            index: action.index,
+            kind: action.kind,
+            executor,
+            reason_hash: action.reason_hash,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn block_address(
+        ctx: Context<BlockAddress>,
+        address: Pubkey,
+        reason: u8,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        ctx.accounts.blocklist_entry.apply_block(address, reason, Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+
+    pub fn unblock_address(
+        ctx: Context<UnblockAddress>,
+        address: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.require_authority(&ctx.accounts.authority.key())?;
+        require_keys_eq!(ctx.accounts.blocklist_entry.address, address, BigBallsError::BlocklistEntryRequired);
+        ctx.accounts.blocklist_entry.apply_unblock(Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+
+    // Creates an inactive entry so governance can later block the address
+    pub fn initialize_blocklist_entry(
+        ctx: Context<InitializeBlocklistEntry>,
+        address: Pubkey,
+    ) -> Result<()> {
+        let entry = &mut ctx.accounts.blocklist_entry;
+        entry.address = address;
+        entry.blocked = false;
+        entry.reason = 0;
+        entry.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    // Token-2022 invokes the hook with the SPL interface discriminator rather
+    // than Anchor's, so route Execute to the handler above
+    pub fn fallback(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        data: &[u8],
+    ) -> Result<()> {
+        match TransferHookInstruction::unpack(data)? {
+            TransferHookInstruction::Execute { amount } => {
+                __private::__global::execute(program_id, accounts, &amount.to_le_bytes())
+            }
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }
+    }
+}
+
+/// Accounts appended to every Execute after source, mint, destination, owner
+/// and the list itself (indices 0-4); the config follows at index 5
+fn transfer_hook_extra_account_metas(config: &Pubkey) -> Result<[ExtraAccountMeta; TRANSFER_HOOK_EXTRA_ACCOUNTS]> {
+    Ok([
+        ExtraAccountMeta::new_with_pubkey(config, false, false)?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"holder_state".to_vec() },
+                Seed::AccountKey { index: 0 },
+            ],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"blocklist".to_vec() },
+                Seed::AccountKey { index: 5 },
+                Seed::AccountKey { index: 3 },
+            ],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"holder_state".to_vec() },
+                Seed::AccountKey { index: 2 },
+            ],
+            false,
+            true,
+        )?,
+        // Destination entries are keyed by the owner wallet, read from the token account
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"blocklist".to_vec() },
+                Seed::AccountKey { index: 5 },
+                Seed::AccountData { account_index: 2, data_index: 32, length: 32 },
+            ],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"exempt".to_vec() },
+                Seed::AccountKey { index: 5 },
+                Seed::AccountData { account_index: 2, data_index: 32, length: 32 },
+            ],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"exempt".to_vec() },
+                Seed::AccountKey { index: 5 },
+                Seed::AccountKey { index: 3 },
+            ],
+            false,
+            false,
+        )?,
+    ])
+}
+
+/// M-of-N sets must be non-empty, bounded, free of duplicates and have a
+/// reachable threshold
+pub fn validate_signer_set(signers: &[Pubkey], threshold: u8, max_signers: usize) -> Result<()> {
+    require!(
+        !signers.is_empty() && signers.len() <= max_signers,
+        BigBallsError::InvalidSignerSet
+    );
+    require!(
+        threshold > 0 && threshold as usize <= signers.len(),
+        BigBallsError::InvalidSignerSet
+    );
+    for (i, signer) in signers.iter().enumerate() {
+        require!(
+            *signer != Pubkey::default() && !signers[..i].contains(signer),
+            BigBallsError::InvalidSignerSet
+        );
+    }
+    Ok(())
+}
+
+/// An address is blocked only if its blocklist entry exists and is active
+fn is_blocked(entry: &AccountInfo) -> Result<bool> {
+    if entry.data_is_empty() {
+        return Ok(false);
+    }
+    Ok(Account::<Blocklist>::try_from(entry)?.blocked)
+}
+
+/// Same convention as `is_blocked`: a missing exemption exempts nothing
+fn exemption_flags(entry: &AccountInfo) -> Result<ExemptionFlags> {
+    if entry.data_is_empty() {
+        return Ok(ExemptionFlags::default());
+    }
+    Ok(Account::<Exemption>::try_from(entry)?.flags())
+}
+
+/// Persist the holder states the hook loaded from unchecked accounts
+fn exit_holder_states<'info>(
+    source_state: Option<Account<'info, HolderState>>,
+    destination_state: Option<Account<'info, HolderState>>,
+) -> Result<()> {
+    for state in source_state.iter().chain(destination_state.iter()) {
+        state.exit(&crate::ID)?;
+    }
+    Ok(())
+}
+
+/// Holder state behind an unchecked hook account, or None if it was never created
+fn load_holder_state<'info>(account: &AccountInfo<'info>) -> Result<Option<Account<'info, HolderState>>> {
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Account::<HolderState>::try_from(account)?))
+}
+
+/// Move tokens with `transfer_checked`, forwarding the transfer hook's extra
+/// accounts from `remaining_accounts` when the mint is Token-2022
+#[allow(clippy::too_many_arguments)]
+fn transfer_checked_with_hook<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from.clone(),
+        mint.clone(),
+        to.clone(),
+        authority.clone(),
+        remaining_accounts,
+        amount,
+        decimals,
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Sweep a Token-2022 account's withheld fees to the mint; accounts holding
+/// withheld fees can't be closed
+fn harvest_withheld_fees<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+) -> Result<()> {
+    if token_program.key() != Token2022::id() {
+        return Ok(());
+    }
+    let withheld_amount = {
+        let data = account.try_borrow_data()?;
+        let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+        state
+            .get_extension::<transfer_fee::TransferFeeAmount>()
+            .map(|fees| u64::from(fees.withheld_amount))
+            .unwrap_or(0)
+    };
+    if withheld_amount > 0 {
+        invoke(
+            &transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+                token_program.key,
+                mint.key,
+                &[account.key],
+            )?,
+            &[mint.clone(), account.clone()],
+        )?;
+    }
+    Ok(())
+}
+
+/// Mirror the configured fee total onto a Token-2022 mint's transfer fee, so
+/// the fee withheld on-chain always matches `total_fee_bp`
+fn sync_transfer_fee<'info>(
+    config: &Account<'info, TokenConfig>,
+    mint: Option<&InterfaceAccount<'info, token_interface::Mint>>,
+    fee_authority: Option<&UncheckedAccount<'info>>,
+    token_program: Option<&Program<'info, Token2022>>,
+    bump: Option<u8>,
+) -> Result<()> {
+    if config.token_program != Token2022::id() {
+        return Ok(());
+    }
+    let (Some(mint), Some(fee_authority), Some(token_program), Some(bump)) =
+        (mint, fee_authority, token_program, bump)
+    else {
+        return err!(BigBallsError::TransferFeeAccountsRequired);
+    };
+
+    let config_key = config.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"fee_authority", config_key.as_ref(), &[bump]]];
+    invoke_signed(
+        &transfer_fee::instruction::set_transfer_fee(
+            token_program.key,
+            &mint.key(),
+            fee_authority.key,
+            &[],
+            config.total_fee_bp(),
+            u64::MAX,
+        )?,
+        &[mint.to_account_info(), fee_authority.to_account_info()],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+fn token_2022_amount(account: &AccountInfo) -> Result<u64> {
+    let data = account.try_borrow_data()?;
+    Ok(StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?.base.amount)
+}
+
+/// Owner wallet of a Token-2022 token account
+fn token_account_owner(account: &AccountInfo) -> Result<Pubkey> {
+    let data = account.try_borrow_data()?;
+    Ok(StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?.base.owner)
+}
+
+/// Fee Token-2022 withholds from `amount` under the mint's current epoch fee
+fn withheld_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    let Ok(fee_config) = mint.get_extension::<transfer_fee::TransferFeeConfig>() else {
+        return Ok(0);
+    };
+    Ok(fee_config
+        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or(BigBallsError::InvalidFeeCalculation)?)
+}
+
+/// Reject hook calls that don't come from an in-flight Token-2022 transfer
+fn assert_is_transferring(source: &AccountInfo) -> Result<()> {
+    require_keys_eq!(*source.owner, spl_token_2022::ID, BigBallsError::NotTransferring);
+
+    let data = source.try_borrow_data()?;
+    let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    let extension = account.get_extension::<transfer_hook::TransferHookAccount>()?;
+    require!(bool::from(extension.transferring), BigBallsError::NotTransferring);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = authority, space = 8 + TokenConfig::LEN)]
+    pub config: Account<'info, TokenConfig>,
+    /// CHECK: allocated by the client and initialized as a mint here
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: PDA set as mint and freeze authority; validated by seeds
+    #[account(seeds = [b"mint_authority", config.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeToken2022<'info> {
+    #[account(init, payer = authority, space = 8 + TokenConfig::LEN)]
+    pub config: Account<'info, TokenConfig>,
+    /// Fresh keypair; created and initialized by this instruction
+    #[account(mut)]
+    pub mint: Signer<'info>,
+    /// CHECK: PDA holding the transfer fee config and withdraw authorities; validated by seeds
+    #[account(seeds = [b"fee_authority", config.key().as_ref()], bump)]
+    pub fee_authority: UncheckedAccount<'info>,
+    /// CHECK: PDA set as mint and freeze authority; validated by seeds
+    #[account(seeds = [b"mint_authority", config.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Transfer<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    #[account(mut, address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(mut)]
+    pub from: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut, constraint = to.key() != from.key() @ BigBallsError::InvalidRecipient)]
+    pub to: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut, address = config.marketing_wallet @ BigBallsError::InvalidMarketingWallet)]
+    pub marketing_wallet: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut, address = config.dev_wallet @ BigBallsError::InvalidDevWallet)]
+    pub dev_wallet: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reflection_pool", config.key().as_ref()],
+        bump
+    )]
+    pub reflection_pool: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + HolderState::LEN,
+        seeds = [b"holder_state", from.key().as_ref()],
+        bump
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + HolderState::LEN,
+        seeds = [b"holder_state", to.key().as_ref()],
+        bump
+    )]
+    pub to_holder_state: Account<'info, HolderState>,
+    #[account(seeds = [b"exempt", config.key().as_ref(), from.owner.as_ref()], bump)]
+    pub from_exemption: Option<Account<'info, Exemption>>,
+    #[account(seeds = [b"exempt", config.key().as_ref(), to.owner.as_ref()], bump)]
+    pub to_exemption: Option<Account<'info, Exemption>>,
+    // Required (unlike exemptions) so a blocked party cannot omit them
+    /// CHECK: blocklist entry for the sender; may be uninitialized
+    #[account(seeds = [b"blocklist", config.key().as_ref(), from.owner.as_ref()], bump)]
+    pub from_blocklist: UncheckedAccount<'info>,
+    /// CHECK: blocklist entry for the recipient; may be uninitialized
+    #[account(seeds = [b"blocklist", config.key().as_ref(), to.owner.as_ref()], bump)]
+    pub to_blocklist: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RenounceAuthority<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateLimits<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetDevWallet<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAmmPools<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct SetExemption<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Exemption::LEN,
+        seeds = [b"exempt", config.key().as_ref(), owner.as_ref()],
+        bump
+    )]
+    pub exemption: Account<'info, Exemption>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct RemoveExemption<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"exempt", config.key().as_ref(), owner.as_ref()],
+        bump
+    )]
+    pub exemption: Account<'info, Exemption>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeReflectionPool<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VaultAuthority::LEN,
+        seeds = [b"reflection_authority", config.key().as_ref()],
+        bump
+    )]
+    pub reflection_authority: Account<'info, VaultAuthority>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reflection_pool", config.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = reflection_authority
+    )]
+    pub reflection_pool: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeWithheldFees<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    #[account(mut, address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// CHECK: PDA holding the transfer fee withdraw authority; validated by seeds
+    #[account(seeds = [b"fee_authority", config.key().as_ref()], bump)]
+    pub fee_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"reflection_pool", config.key().as_ref()],
+        bump
+    )]
+    pub reflection_pool: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(seeds = [b"reflection_authority", config.key().as_ref()], bump)]
+    pub reflection_authority: Account<'info, VaultAuthority>,
+    #[account(mut, address = config.marketing_wallet @ BigBallsError::InvalidMarketingWallet)]
+    pub marketing_wallet: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut, address = config.dev_wallet @ BigBallsError::InvalidDevWallet)]
+    pub dev_wallet: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(address = config.token_program)]
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReflections<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        mut,
+        constraint = holder_token_account.owner == holder.key() @ BigBallsError::Unauthorized,
+        constraint = holder_token_account.mint == config.mint @ BigBallsError::InvalidAmount
+    )]
+    pub holder_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = 8 + HolderState::LEN,
+        seeds = [b"holder_state", holder_token_account.key().as_ref()],
+        bump
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    #[account(
+        mut,
+        seeds = [b"reflection_pool", config.key().as_ref()],
+        bump
+    )]
+    pub reflection_pool: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(seeds = [b"reflection_authority", config.key().as_ref()], bump)]
+    pub reflection_authority: Account<'info, VaultAuthority>,
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct CreateVesting<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VestingSchedule::LEN,
+        seeds = [b"vesting", config.key().as_ref(), beneficiary.as_ref()],
+        bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + VaultAuthority::LEN,
+        seeds = [b"vesting_authority", config.key().as_ref()],
+        bump
+    )]
+    pub vesting_authority: Account<'info, VaultAuthority>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vesting_vault", vesting_schedule.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vesting_authority
+    )]
+    pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        mut,
+        constraint = source.owner == authority.key() @ BigBallsError::Unauthorized,
+        constraint = source.mint == config.mint @ BigBallsError::InvalidAmount
+    )]
+    pub source: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseVested<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        mut,
+        seeds = [b"vesting", config.key().as_ref(), beneficiary.key().as_ref()],
+        bump,
+        has_one = config,
+        has_one = beneficiary,
+        has_one = vault
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(seeds = [b"vesting_authority", config.key().as_ref()], bump)]
+    pub vesting_authority: Account<'info, VaultAuthority>,
+    #[account(
+        mut,
+        constraint = beneficiary_token_account.owner == beneficiary.key() @ BigBallsError::Unauthorized,
+        constraint = beneficiary_token_account.mint == config.mint @ BigBallsError::InvalidAmount
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub beneficiary: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+// Lock vaults are not tied to the token config so any SPL token, such as
+// AMM LP tokens, can be locked
+#[derive(Accounts)]
+#[instruction(lock_id: u64)]
+pub struct Lock<'info> {
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + LockVault::LEN,
+        seeds = [b"lock_vault", owner.key().as_ref(), mint.key().as_ref(), lock_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub lock_vault: Account<'info, LockVault>,
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"lock_vault_tokens", lock_vault.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = lock_vault
+    )]
+    pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        mut,
+        constraint = source.owner == owner.key() @ BigBallsError::Unauthorized,
+        constraint = source.mint == mint.key() @ BigBallsError::InvalidAmount
+    )]
+    pub source: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    #[account(mut, has_one = owner @ BigBallsError::Unauthorized)]
+    pub lock_vault: Account<'info, LockVault>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Unlock<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"lock_vault",
+            lock_vault.owner.as_ref(),
+            lock_vault.mint.as_ref(),
+            lock_vault.lock_id.to_le_bytes().as_ref()
+        ],
+        bump,
+        has_one = owner @ BigBallsError::Unauthorized,
+        has_one = vault,
+        close = owner
+    )]
+    pub lock_vault: Account<'info, LockVault>,
+    // Writable so withheld Token-2022 fees can be harvested before closing
+    #[account(mut, address = lock_vault.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        mut,
+        constraint = destination.mint == lock_vault.mint @ BigBallsError::InvalidAmount
+    )]
+    pub destination: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeMarketingVault<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MarketingMultisig::LEN,
+        seeds = [b"marketing_multisig", config.key().as_ref()],
+        bump
+    )]
+    pub marketing_multisig: Account<'info, MarketingMultisig>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VaultAuthority::LEN,
+        seeds = [b"marketing_authority", config.key().as_ref()],
+        bump
+    )]
+    pub marketing_authority: Account<'info, VaultAuthority>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"marketing_vault", config.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = marketing_authority
+    )]
+    pub marketing_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeMarketingAction<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(mut, seeds = [b"marketing_multisig", config.key().as_ref()], bump)]
+    pub marketing_multisig: Account<'info, MarketingMultisig>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + MarketingProposal::LEN,
+        seeds = [
+            b"marketing_proposal",
+            marketing_multisig.key().as_ref(),
+            marketing_multisig.proposal_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub marketing_proposal: Account<'info, MarketingProposal>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveMarketingProposal<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(seeds = [b"marketing_multisig", config.key().as_ref()], bump)]
+    pub marketing_multisig: Account<'info, MarketingMultisig>,
+    #[account(
+        mut,
+        constraint = marketing_proposal.multisig == marketing_multisig.key() @ BigBallsError::Unauthorized
+    )]
+    pub marketing_proposal: Account<'info, MarketingProposal>,
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteMarketingProposal<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        mut,
+        seeds = [b"marketing_multisig", config.key().as_ref()],
+        bump,
+        constraint = marketing_multisig.vault == marketing_vault.key() @ BigBallsError::InvalidMarketingWallet
+    )]
+    pub marketing_multisig: Account<'info, MarketingMultisig>,
+    #[account(
+        mut,
+        constraint = marketing_proposal.multisig == marketing_multisig.key() @ BigBallsError::Unauthorized
+    )]
+    pub marketing_proposal: Account<'info, MarketingProposal>,
+    #[account(mut)]
+    pub marketing_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(seeds = [b"marketing_authority", config.key().as_ref()], bump)]
+    pub marketing_authority: Account<'info, VaultAuthority>,
+    // Only required for Spend proposals
+    #[account(mut)]
+    pub recipient: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    pub executor: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Governance::LEN,
+        seeds = [b"governance", config.key().as_ref()],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vote_vault", config.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = governance
+    )]
+    pub vote_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(mut, seeds = [b"governance", config.key().as_ref()], bump)]
+    pub governance: Account<'info, Governance>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::LEN,
+        seeds = [b"proposal", governance.key().as_ref(), governance.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    // The proposal stake is escrowed voting power, locked until voting ends
+    #[account(
+        mut,
+        seeds = [b"voter", governance.key().as_ref(), proposer.key().as_ref()],
+        bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(seeds = [b"governance", config.key().as_ref()], bump)]
+    pub governance: Account<'info, Governance>,
+    #[account(mut, has_one = governance)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::LEN,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    #[account(
+        mut,
+        seeds = [b"voter", governance.key().as_ref(), voter.key().as_ref()],
+        bump,
+        has_one = voter
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(seeds = [b"governance", config.key().as_ref()], bump)]
+    pub governance: Account<'info, Governance>,
+    #[account(mut, has_one = governance)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    #[account(seeds = [b"governance", config.key().as_ref()], bump)]
+    pub governance: Account<'info, Governance>,
+    #[account(mut, has_one = governance)]
+    pub proposal: Account<'info, Proposal>,
+    // Only required for ScheduleBurn and CancelBurn proposals
+    #[account(mut, seeds = [b"treasury", config.key().as_ref()], bump)]
+    pub treasury: Option<Account<'info, Treasury>>,
+    // Only required for BlockAddress and UnblockAddress proposals
+    #[account(mut, seeds = [b"blocklist", config.key().as_ref(), blocklist_entry.address.as_ref()], bump)]
+    pub blocklist_entry: Option<Account<'info, Blocklist>>,
+    // Only required for SetExemption proposals; see initialize_exemption
+    #[account(mut, seeds = [b"exempt", config.key().as_ref(), exemption.owner.as_ref()], bump)]
+    pub exemption: Option<Account<'info, Exemption>>,
+    // Only required for UpdateFees proposals on Token-2022 mints
+    #[account(mut, address = config.mint)]
+    pub mint: Option<InterfaceAccount<'info, token_interface::Mint>>,
+    /// CHECK: PDA holding the transfer fee config authority; validated by seeds
+    #[account(seeds = [b"fee_authority", config.key().as_ref()], bump)]
+    pub fee_authority: Option<UncheckedAccount<'info>>,
+    pub token_program: Option<Program<'info, Token2022>>,
+}
+
+// Voting power is non-transferable: it lives in a PDA keyed by the voter and
+// can only be withdrawn back to the token account it was deposited from
+#[derive(Accounts)]
+pub struct DepositVotes<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(seeds = [b"governance", config.key().as_ref()], bump)]
+    pub governance: Account<'info, Governance>,
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + VoterRecord::LEN,
+        seeds = [b"voter", governance.key().as_ref(), voter.key().as_ref()],
+        bump,
+        constraint = voter_record.token_account == Pubkey::default()
+            || voter_record.token_account == voter_token_account.key() @ BigBallsError::InvalidVoterTokenAccount
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    #[account(mut, address = governance.vote_vault)]
+    pub vote_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        mut,
+        constraint = voter_token_account.owner == voter.key() @ BigBallsError::Unauthorized,
+        constraint = voter_token_account.mint == config.mint @ BigBallsError::InvalidAmount
+    )]
+    pub voter_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + HolderState::LEN,
+        seeds = [b"holder_state", voter_token_account.key().as_ref()],
+        bump
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawVotes<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(seeds = [b"governance", config.key().as_ref()], bump)]
+    pub governance: Account<'info, Governance>,
+    #[account(
+        mut,
+        seeds = [b"voter", governance.key().as_ref(), voter.key().as_ref()],
+        bump,
+        has_one = voter,
+        constraint = voter_record.token_account == voter_token_account.key() @ BigBallsError::InvalidVoterTokenAccount
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    #[account(mut, address = governance.vote_vault)]
+    pub vote_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut)]
+    pub voter_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"holder_state", voter_token_account.key().as_ref()],
+        bump
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    pub voter: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Treasury::LEN,
+        seeds = [b"treasury", config.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"treasury_vault", config.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = treasury
+    )]
+    pub treasury_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Permissionless once the notice period has elapsed
+#[derive(Accounts)]
+pub struct ExecuteBurn<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    #[account(mut, address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(mut, seeds = [b"treasury", config.key().as_ref()], bump)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, address = treasury.vault)]
+    pub treasury_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePriceOracle<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PriceOracle::LEN,
+        seeds = [b"price_oracle", config.key().as_ref()],
+        bump
+    )]
+    pub price_oracle: Account<'info, PriceOracle>,
+    #[account(
+        constraint = config.amm_pools.contains(&pool_token_account.key()) @ BigBallsError::AmmPoolNotFound,
+        constraint = pool_token_account.mint == config.mint @ BigBallsError::AmmPoolNotFound
+    )]
+    pub pool_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    // The quote side must belong to the same pool authority
+    #[account(
+        constraint = pool_quote_account.owner == pool_token_account.owner @ BigBallsError::AmmPoolNotFound,
+        constraint = pool_quote_account.mint != config.mint @ BigBallsError::AmmPoolNotFound
+    )]
+    pub pool_quote_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCircuitBreaker<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(mut, seeds = [b"price_oracle", config.key().as_ref()], bump)]
+    pub price_oracle: Account<'info, PriceOracle>,
+    pub authority: Signer<'info>,
+}
+
+// Permissionless crank; anyone may feed observations, but a trip needs a
+// drop confirmed across several spaced observations
+#[derive(Accounts)]
+pub struct CheckCircuitBreaker<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    #[account(mut, seeds = [b"price_oracle", config.key().as_ref()], bump)]
+    pub price_oracle: Account<'info, PriceOracle>,
+    #[account(address = price_oracle.pool_token_account)]
+    pub pool_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(address = price_oracle.pool_quote_account)]
+    pub pool_quote_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGuardianSet<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GuardianSet::LEN,
+        seeds = [b"guardian_set", config.key().as_ref()],
+        bump
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeEmergencyAction<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(mut, seeds = [b"guardian_set", config.key().as_ref()], bump)]
+    pub guardian_set: Account<'info, GuardianSet>,
+    #[account(
+        init,
+        payer = guardian,
+        space = 8 + EmergencyAction::LEN,
+        seeds = [
+            b"emergency_action",
+            guardian_set.key().as_ref(),
+            guardian_set.action_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub emergency_action: Account<'info, EmergencyAction>,
+    #[account(mut)]
+    pub guardian: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveEmergencyAction<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(seeds = [b"guardian_set", config.key().as_ref()], bump)]
+    pub guardian_set: Account<'info, GuardianSet>,
+    #[account(mut, has_one = guardian_set)]
+    pub emergency_action: Account<'info, EmergencyAction>,
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteEmergencyAction<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    #[account(seeds = [b"guardian_set", config.key().as_ref()], bump)]
+    pub guardian_set: Account<'info, GuardianSet>,
+    #[account(mut, has_one = guardian_set)]
+    pub emergency_action: Account<'info, EmergencyAction>,
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct BlockAddress<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Blocklist::LEN,
+        seeds = [b"blocklist", config.key().as_ref(), address.as_ref()],
+        bump
+    )]
+    pub blocklist_entry: Account<'info, Blocklist>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct UnblockAddress<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(mut, seeds = [b"blocklist", config.key().as_ref(), address.as_ref()], bump)]
+    pub blocklist_entry: Account<'info, Blocklist>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct InitializeExemption<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Exemption::LEN,
+        seeds = [b"exempt", config.key().as_ref(), owner.as_ref()],
+        bump
+    )]
+    pub exemption: Account<'info, Exemption>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct InitializeBlocklistEntry<'info> {
+    pub config: Account<'info, TokenConfig>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Blocklist::LEN,
+        seeds = [b"blocklist", config.key().as_ref(), address.as_ref()],
+        bump
+    )]
+    pub blocklist_entry: Account<'info, Blocklist>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    pub config: Account<'info, TokenConfig>,
+    /// CHECK: Token-2022 mint recorded on the config
+    #[account(address = config.mint)]
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: TLV list of extra accounts, written by this instruction
+    #[account(
+        init,
+        payer = authority,
+        space = ExtraAccountMetaList::size_of(TRANSFER_HOOK_EXTRA_ACCOUNTS)?,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateExtraAccountMetaList<'info> {
+    pub config: Account<'info, TokenConfig>,
+    /// CHECK: Token-2022 mint recorded on the config
+    #[account(address = config.mint)]
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: TLV list of extra accounts, resized and rewritten by this instruction
+    #[account(
+        mut,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeHolderState<'info> {
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + HolderState::LEN,
+        seeds = [b"holder_state", token_account.key().as_ref()],
+        bump
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Account order is fixed by the SPL transfer-hook interface
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    /// CHECK: source token account; verified as mid-transfer by assert_is_transferring
+    pub source: UncheckedAccount<'info>,
+    /// CHECK: Token-2022 mint recorded on the config
+    #[account(address = config.mint)]
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: destination token account; validated by Token-2022
+    pub destination: UncheckedAccount<'info>,
+    /// CHECK: source owner or delegate; validated by Token-2022
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: extra account meta list; validated by seeds
+    #[account(seeds = [b"extra-account-metas", mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    pub config: Account<'info, TokenConfig>,
+    /// CHECK: holder state for the source; loaded by the hook, required for
+    /// transfers not sent by a program vault
+    #[account(
+        mut,
+        seeds = [b"holder_state", source.key().as_ref()],
+        bump
+    )]
+    pub holder_state: UncheckedAccount<'info>,
+    /// CHECK: blocklist entry for the owner; may be uninitialized
+    #[account(seeds = [b"blocklist", config.key().as_ref(), owner.key().as_ref()], bump)]
+    pub owner_blocklist: UncheckedAccount<'info>,
+    /// CHECK: holder state for the destination; checkpointed only if initialized
+    #[account(
+        mut,
+        seeds = [b"holder_state", destination.key().as_ref()],
+        bump
+    )]
+    pub destination_holder_state: UncheckedAccount<'info>,
+    /// CHECK: blocklist entry for the destination's owner wallet; may be uninitialized
+    #[account(seeds = [b"blocklist", config.key().as_ref(), token_account_owner(&destination)?.as_ref()], bump)]
+    pub destination_blocklist: UncheckedAccount<'info>,
+    /// CHECK: exemption for the destination's owner wallet; may be uninitialized
+    #[account(seeds = [b"exempt", config.key().as_ref(), token_account_owner(&destination)?.as_ref()], bump)]
+    pub destination_exemption: UncheckedAccount<'info>,
+    /// CHECK: exemption for the owner; may be uninitialized
+    #[account(seeds = [b"exempt", config.key().as_ref(), owner.key().as_ref()], bump)]
+    pub owner_exemption: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MintInitialSupply<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    #[account(mut, address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// CHECK: PDA mint authority; validated by seeds
+    #[account(seeds = [b"mint_authority", config.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    #[account(mut, token::mint = mint)]
+    pub destination: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+// Destination token accounts are passed as remaining accounts, in allocation order
+#[derive(Accounts)]
+pub struct DistributeInitialSupply<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    #[account(mut, address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// CHECK: PDA mint authority; validated by seeds
+    #[account(seeds = [b"mint_authority", config.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeMintAuthority<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    #[account(mut, address = config.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// CHECK: PDA mint authority; validated by seeds
+    #[account(seeds = [b"mint_authority", config.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+    #[account(address = config.token_program)]
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetTrading<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFees<'info> {
+    #[account(mut)]
+    pub config: Account<'info, TokenConfig>,
+    pub authority: Signer<'info>,
+    // Only required for Token-2022 mints, whose on-chain transfer fee is kept in sync
+    #[account(mut, address = config.mint)]
+    pub mint: Option<InterfaceAccount<'info, token_interface::Mint>>,
+    /// CHECK: PDA holding the transfer fee config authority; validated by seeds
+    #[account(seeds = [b"fee_authority", config.key().as_ref()], bump)]
+    pub fee_authority: Option<UncheckedAccount<'info>>,
+    pub token_program: Option<Program<'info, Token2022>>,
+}
+
+#[account]
+#[derive(Default)]
+pub struct TokenConfig {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub total_supply: u64,
+    pub pause_flags: u8,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub marketing_wallet: Pubkey,
+    pub dev_wallet: Pubkey,
+    
+    // Supply issuance
+    pub initial_supply_minted: bool,
+    pub mint_authority_revoked: bool,
+    
+    // Burn tracking
+    pub total_burned: u64,
+    
+    // Reflection accumulator (scaled by REFLECTION_PRECISION)
+    pub reflection_per_token: u128,
+    pub total_reflections: u64,
+    
+    // Trading limits
+    pub max_transaction_amount: u64,
+    pub max_wallet_amount: u64,
+    
+    // Rolling 24h volume caps per wallet
+    pub daily_buy_limit: u64,
+    pub daily_sell_limit: u64,
+    
+    // Registered AMM pool token accounts used to classify buys and sells
+    pub amm_pools: Vec<Pubkey>,
+    
+    // Fee configuration (in basis points)
     pub reflection_fee_bp: u16,
     pub marketing_fee_bp: u16,
     pub burn_fee_bp: u16,
@@ -286,40 +3707,1459 @@ pub struct TokenConfig {
     pub buy_cooldown: i64,
     pub sell_cooldown: i64,
     pub transaction_cooldown: i64,
+    
+    // Launch schedule; the limits above apply once the launch phase ends
+    pub trading_enabled_at: i64,
+    pub launch_phase_duration: i64,
+    pub launch_max_transaction_amount: u64,
+    pub launch_max_wallet_amount: u64,
+    pub launch_transaction_cooldown: i64,
+    
+    // Anti-sniper window, counted in slots from when trading was enabled
+    pub trading_enabled_slot: u64,
+    pub anti_sniper_slots: u64,
+    pub anti_sniper_fee_bp: u16,
+    pub anti_sniper_block: bool,
+    
+    // Circuit breaker pause; transfers resume automatically at paused_until
+    pub paused_until: i64,
+    pub pause_reason: u8,
+    
+    // Set and cleared only by guardian emergency actions
+    pub emergency_shutdown: bool,
+    
+    // Bumps of the vote and treasury vaults, recorded when they are created
+    pub vote_vault_bump: Option<u8>,
+    pub treasury_vault_bump: Option<u8>,
+}
+
+#[account]
+#[derive(Default)]
+pub struct HolderState {
+    pub last_transaction: i64,
+    pub total_transactions: u64,
+    pub total_amount: u64,
+    pub last_reflection_claim: i64,
+    
+    // Direction-specific cooldown tracking
+    pub last_buy: i64,
+    pub last_sell: i64,
+    
+    // Rolling 24h buy/sell volume
+    pub buy_volume: VolumeWindow,
+    pub sell_volume: VolumeWindow,
+    
+    // Reflection checkpoint against TokenConfig::reflection_per_token, and the
+    // token account balance recorded at that checkpoint
+    pub reflection_checkpoint: u128,
+    pub checkpoint_balance: u64,
+    pub pending_reflections: u64,
+    pub initialized: bool,
+    
+    // Tokens held in the governance vote vault on behalf of this account
+    pub escrowed_amount: u64,
+    
+    // Slot of the last transfer this wallet initiated
+    pub last_transfer_slot: u64,
+}
+
+#[account]
+pub struct Exemption {
+    pub owner: Pubkey,
+    pub fee_exempt: bool,
+    pub limit_exempt: bool,
+    pub cooldown_exempt: bool,
+    // Anti-sniper whitelist, kept apart from the trading limit exemption
+    pub sniper_whitelisted: bool,
+}
+
+#[account]
+pub struct VestingSchedule {
+    pub config: Pubkey,
+    pub beneficiary: Pubkey,
+    pub vault: Pubkey,
+    pub total_amount: u64,
+    pub released_amount: u64,
+    
+    // Nothing unlocks before the cliff; vesting is linear from start_time
+    pub start_time: i64,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+}
+
+#[account]
+pub struct LockVault {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub lock_id: u64,
+    pub amount: u64,
+    pub locked_at: i64,
+    pub unlock_time: i64,
+}
+
+#[account]
+pub struct MarketingMultisig {
+    pub config: Pubkey,
+    pub vault: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    
+    // Spends above timelock_threshold, and every change to the multisig
+    // itself, wait timelock_duration after approval
+    pub timelock_threshold: u64,
+    pub timelock_duration: i64,
+    pub proposal_count: u64,
+}
+
+#[account]
+pub struct MarketingProposal {
+    pub multisig: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: MarketingAction,
+    pub approvals: Vec<Pubkey>,
+    pub created_at: i64,
+    pub executable_at: i64,
+    pub executed: bool,
+}
+
+#[account]
+pub struct Governance {
+    pub config: Pubkey,
+    pub vote_vault: Pubkey,
+    pub proposal_count: u64,
+    
+    // Voting rules; thresholds in basis points of circulating supply or votes cast
+    pub proposal_threshold: u64,
+    pub voting_period: i64,
+    pub quorum_bp: u16,
+    pub approval_bp: u16,
+    pub execution_delay: i64,
+}
+
+#[account]
+pub struct Proposal {
+    pub governance: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub description_hash: [u8; 32],
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub created_at: i64,
+    pub voting_ends_at: i64,
+    pub executable_at: i64,
+    pub status: ProposalStatus,
+}
+
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub weight: u64,
+    pub support: bool,
+}
+
+#[account]
+pub struct VoterRecord {
+    pub voter: Pubkey,
+    pub token_account: Pubkey,
+    pub voting_power: u64,
+    
+    // Latest voting deadline among proposals this voter has voted on or opened
+    pub locked_until: i64,
+    // Voting deadline of the proposal this voter's stake currently backs
+    pub active_proposal_ends_at: i64,
+}
+
+#[account]
+pub struct Treasury {
+    pub config: Pubkey,
+    pub vault: Pubkey,
+    
+    // At most one manual burn is pending at a time
+    pub pending_burn_amount: u64,
+    pub burn_executable_at: i64,
+    pub burn_count: u64,
+}
+
+#[account]
+pub struct PriceOracle {
+    pub config: Pubkey,
+    pub pool_token_account: Pubkey,
+    pub pool_quote_account: Pubkey,
+    
+    // Trip when the price falls drop_threshold_bp below its peak within window
+    pub drop_threshold_bp: u16,
+    pub window: i64,
+    pub pause_duration: i64,
+    
+    // Ring buffer of recent pool prices
+    pub observations: [PriceObservation; PRICE_OBSERVATIONS],
+    pub next_index: u8,
+}
+
+#[account]
+pub struct GuardianSet {
+    pub config: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timelock_duration: i64,
+    pub action_count: u64,
+}
+
+#[account]
+pub struct EmergencyAction {
+    pub guardian_set: Pubkey,
+    pub index: u64,
+    pub kind: EmergencyActionKind,
+    pub reason_hash: [u8; 32],
+    pub reason_uri: String,
+    pub proposer: Pubkey,
+    pub approvals: Vec<Pubkey>,
+    pub created_at: i64,
+    pub executable_at: i64,
+    pub executed: bool,
+}
+
+#[account]
+pub struct Blocklist {
+    pub address: Pubkey,
+    pub blocked: bool,
+    pub reason: u8,
+    pub updated_at: i64,
+}
+
+// Program-owned signer for token vaults; the transfer hook recognises its
+// transfers as program-initiated by the account owner
+#[account]
+#[derive(Default)]
+pub struct VaultAuthority {}
+
+impl VaultAuthority {
+    pub const LEN: usize = 0;
+}
+
+impl TokenConfig {
+    pub const LEN: usize = 32 + // name
+                          32 + // symbol
+                          1 +  // decimals
+                          8 +  // total_supply
+                          1 +  // pause_flags
+                          32 + // authority
+                          32 + // pending_authority
+                          32 + // mint
+                          32 + // token_program
+                          32 + // marketing_wallet
+                          32 + // dev_wallet
+                          1 +  // initial_supply_minted
+                          1 +  // mint_authority_revoked
+                          8 +  // total_burned
+                          16 + // reflection_per_token
+                          8 +  // total_reflections
+                          8 +  // max_transaction_amount
+                          8 +  // max_wallet_amount
+                          8 +  // daily_buy_limit
+                          8 +  // daily_sell_limit
+                          4 + 32 * MAX_AMM_POOLS + // amm_pools
+                          2 +  // reflection_fee_bp
+                          2 +  // marketing_fee_bp
+                          2 +  // burn_fee_bp
+                          2 +  // dev_fee_bp
+                          8 +  // buy_cooldown
+                          8 +  // sell_cooldown
+                          8 +  // transaction_cooldown
+                          8 +  // trading_enabled_at
+                          8 +  // launch_phase_duration
+                          8 +  // launch_max_transaction_amount
+                          8 +  // launch_max_wallet_amount
+                          8 +  // launch_transaction_cooldown
+                          8 +  // trading_enabled_slot
+                          8 +  // anti_sniper_slots
+                          2 +  // anti_sniper_fee_bp
+                          1 +  // anti_sniper_block
+                          8 +  // paused_until
+                          1 +  // pause_reason
+                          1 +  // emergency_shutdown
+                          2 +  // vote_vault_bump
+                          2;   // treasury_vault_bump
+
+    /// Validate the fee wallets and apply the launch defaults from the
+    /// tokenomics spec; callers set authority, mint and token program
+    pub fn init_defaults(
+        &mut self,
+        name: String,
+        symbol: String,
+        decimals: u8,
+        marketing_wallet: Pubkey,
+        dev_wallet: Pubkey,
+    ) -> Result<()> {
+        // Validate marketing wallet
+        require!(
+            marketing_wallet != Pubkey::default(),
+            BigBallsError::InvalidMarketingWallet
+        );
+
+        // Validate dev wallet
+        require!(
+            dev_wallet != Pubkey::default(),
+            BigBallsError::InvalidDevWallet
+        );
+
+        // Basic token configuration
+        self.name = name;
+        self.symbol = symbol;
+        self.decimals = decimals;
+        self.total_supply = 1_000_000_000 * 10u64.pow(decimals as u32);  // 1 billion tokens
+        self.authority = Pubkey::default();
+        self.pending_authority = Pubkey::default();
+        self.mint = Pubkey::default();
+        self.token_program = Pubkey::default();
+        self.marketing_wallet = marketing_wallet;
+        self.dev_wallet = dev_wallet;
+        self.initial_supply_minted = false;
+        self.mint_authority_revoked = false;
+        self.total_burned = 0;
+        self.reflection_per_token = 0;
+        self.total_reflections = 0;
+        
+        // Trading parameters
+        self.pause_flags = PAUSE_TRADING;  // trading opens via set_trading_enabled
+        self.trading_enabled_at = 0;
+        self.max_transaction_amount = self.total_supply / 100;  // 1% of total supply
+        self.max_wallet_amount = self.total_supply * 3 / 100;  // 3% of total supply
+        self.daily_buy_limit = self.total_supply / 20;   // 5% of total supply
+        self.daily_sell_limit = self.total_supply / 50;  // 2% of total supply
+        self.amm_pools = Vec::new();
+        
+        // Fee configuration (in basis points)
+        self.reflection_fee_bp = 200;     // 2%
+        self.marketing_fee_bp = 150;      // 1.5%
+        self.burn_fee_bp = 100;           // 1%
+        self.dev_fee_bp = 50;             // 0.5%
+        
+        // Cooldown periods (in seconds)
+        self.buy_cooldown = 300;          // 5 minutes
+        self.sell_cooldown = 1800;        // 30 minutes
+        self.transaction_cooldown = 30;    // 30 seconds
+        
+        // Launch phase anti-bot limits (first 24 hours after trading is enabled)
+        self.launch_phase_duration = SECONDS_PER_DAY;
+        self.launch_max_transaction_amount = self.total_supply / 1000; // 0.1% of total supply
+        self.launch_max_wallet_amount = self.total_supply / 100;      // 1% of total supply
+        self.launch_transaction_cooldown = 60;                          // 1 minute
+        
+        // Anti-sniper: buys in the first 5 slots after launch pay a 25% burn
+        self.trading_enabled_slot = 0;
+        self.anti_sniper_slots = 5;
+        self.anti_sniper_fee_bp = 2500;
+        self.anti_sniper_block = false;
+        
+        // No circuit breaker pause in effect
+        self.paused_until = 0;
+        self.pause_reason = PAUSE_REASON_NONE;
+        self.emergency_shutdown = false;
+        
+        // Pooled vaults don't exist until governance and the treasury are set up
+        self.vote_vault_bump = None;
+        self.treasury_vault_bump = None;
+
+        Ok(())
+    }
+
+    /// Whether `slot` falls within the anti-sniper window after launch
+    pub fn in_anti_sniper_window(&self, slot: u64) -> bool {
+        self.trading_enabled_at != 0 && slot < self.trading_enabled_slot + self.anti_sniper_slots
+    }
+
+    /// Whether a circuit breaker pause is still in effect at `now`
+    pub fn is_paused(&self, now: i64) -> bool {
+        now < self.paused_until
+    }
+
+    /// Reset the pause state once a circuit breaker pause has run out
+    pub fn clear_expired_pause(&mut self, now: i64) {
+        if self.paused_until != 0 && !self.is_paused(now) {
+            self.paused_until = 0;
+            self.pause_reason = PAUSE_REASON_NONE;
+        }
+    }
+
+    /// Combined fee across reflection, marketing, burn and dev shares
+    pub fn total_fee_bp(&self) -> u16 {
+        self.reflection_fee_bp + self.marketing_fee_bp + self.burn_fee_bp + self.dev_fee_bp
+    }
+
+    /// Admin instructions require the current authority; a renounced
+    /// config (default authority) rejects every caller
+    pub fn require_authority(&self, signer: &Pubkey) -> Result<()> {
+        require!(
+            self.authority != Pubkey::default(),
+            BigBallsError::AuthorityRenounced
+        );
+        require!(*signer == self.authority, BigBallsError::Unauthorized);
+        Ok(())
+    }
+
+    /// Trading is enabled when no buy, sell or transfer pause is set
+    pub fn trading_enabled(&self) -> bool {
+        self.pause_flags & PAUSE_TRADING == 0
+    }
+
+    pub fn is_action_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
+    /// Shared by `set_pause_flags` and governance execution
+    pub fn apply_pause_flags(&mut self, flags: u8, now: i64, slot: u64) -> Result<()> {
+        require!(flags & !PAUSE_ALL == 0, BigBallsError::InvalidPauseFlags);
+
+        let old_flags = self.pause_flags;
+        self.pause_flags = flags;
+
+        // The launch phase starts the first time buys are allowed
+        if !self.is_action_paused(PAUSE_BUYS) && self.trading_enabled_at == 0 {
+            self.trading_enabled_at = now;
+            self.trading_enabled_slot = slot;
+        }
+
+        emit!(PauseFlagsUpdated {
+            old_flags,
+            new_flags: flags,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Shared by `set_trading_enabled` and governance execution; toggles
+    /// buys, sells and transfers together
+    pub fn apply_trading_enabled(&mut self, enabled: bool, now: i64, slot: u64) -> Result<()> {
+        let flags = if enabled {
+            self.pause_flags & !PAUSE_TRADING
+        } else {
+            self.pause_flags | PAUSE_TRADING
+        };
+        self.apply_pause_flags(flags, now, slot)?;
+
+        emit!(TradingStatusChanged {
+            enabled,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Shared by `update_fees` and governance execution
+    pub fn apply_fees(
+        &mut self,
+        reflection_fee_bp: u16,
+        marketing_fee_bp: u16,
+        burn_fee_bp: u16,
+        dev_fee_bp: u16,
+        now: i64,
+    ) -> Result<()> {
+        // Validate total fees don't exceed reasonable limit (e.g., 10%)
+        let total_fee_bp = reflection_fee_bp + marketing_fee_bp + burn_fee_bp + dev_fee_bp;
+        require!(total_fee_bp <= 1000, BigBallsError::InvalidFeeConfiguration);
+
+        self.reflection_fee_bp = reflection_fee_bp;
+        self.marketing_fee_bp = marketing_fee_bp;
+        self.burn_fee_bp = burn_fee_bp;
+        self.dev_fee_bp = dev_fee_bp;
+
+        emit!(FeesUpdated {
+            reflection_fee_bp,
+            marketing_fee_bp,
+            burn_fee_bp,
+            dev_fee_bp,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Complete a two-step authority transfer on behalf of `new_authority`
+    pub fn accept_pending_authority(&mut self, new_authority: &Pubkey, now: i64) -> Result<()> {
+        require!(
+            self.pending_authority != Pubkey::default(),
+            BigBallsError::NoPendingAuthority
+        );
+        require!(*new_authority == self.pending_authority, BigBallsError::Unauthorized);
+
+        let old_authority = self.authority;
+        self.authority = self.pending_authority;
+        self.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: self.authority,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Shared by `propose_authority` and governance execution
+    pub fn apply_pending_authority(&mut self, new_authority: Pubkey, now: i64) -> Result<()> {
+        require!(
+            new_authority != Pubkey::default(),
+            BigBallsError::InvalidAuthority
+        );
+        self.pending_authority = new_authority;
+
+        emit!(AuthorityProposed {
+            current_authority: self.authority,
+            pending_authority: new_authority,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Shared by `update_limits` and governance execution
+    pub fn apply_limits(
+        &mut self,
+        max_transaction_amount: u64,
+        max_wallet_amount: u64,
+        buy_cooldown: i64,
+        sell_cooldown: i64,
+        transaction_cooldown: i64,
+        now: i64,
+    ) -> Result<()> {
+        // Validate limits stay within a range that keeps trading possible
+        let min_limit = self.total_supply * MIN_LIMIT_BP / 10000;
+        require!(
+            max_transaction_amount >= min_limit && max_transaction_amount <= self.total_supply,
+            BigBallsError::InvalidLimitConfiguration
+        );
+        require!(
+            max_wallet_amount >= max_transaction_amount && max_wallet_amount <= self.total_supply,
+            BigBallsError::InvalidLimitConfiguration
+        );
+        for cooldown in [buy_cooldown, sell_cooldown, transaction_cooldown] {
+            require!(
+                (0..=SECONDS_PER_DAY).contains(&cooldown),
+                BigBallsError::InvalidLimitConfiguration
+            );
+        }
+
+        self.max_transaction_amount = max_transaction_amount;
+        self.max_wallet_amount = max_wallet_amount;
+        self.buy_cooldown = buy_cooldown;
+        self.sell_cooldown = sell_cooldown;
+        self.transaction_cooldown = transaction_cooldown;
+
+        emit!(LimitsUpdated {
+            max_transaction_amount,
+            max_wallet_amount,
+            buy_cooldown,
+            sell_cooldown,
+            transaction_cooldown,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Shared by `update_launch_limits` and governance execution
+    pub fn apply_launch_limits(
+        &mut self,
+        launch_phase_duration: i64,
+        launch_max_transaction_amount: u64,
+        launch_max_wallet_amount: u64,
+        launch_transaction_cooldown: i64,
+        now: i64,
+    ) -> Result<()> {
+        // Same bounds as the post-launch limits, plus a cap on the phase length
+        let min_limit = self.total_supply * MIN_LIMIT_BP / 10000;
+        require!(
+            (0..=MAX_LAUNCH_PHASE_DURATION).contains(&launch_phase_duration),
+            BigBallsError::InvalidLimitConfiguration
+        );
+        require!(
+            launch_max_transaction_amount >= min_limit && launch_max_transaction_amount <= self.total_supply,
+            BigBallsError::InvalidLimitConfiguration
+        );
+        require!(
+            launch_max_wallet_amount >= launch_max_transaction_amount && launch_max_wallet_amount <= self.total_supply,
+            BigBallsError::InvalidLimitConfiguration
+        );
+        require!(
+            (0..=SECONDS_PER_DAY).contains(&launch_transaction_cooldown),
+            BigBallsError::InvalidLimitConfiguration
+        );
+
+        self.launch_phase_duration = launch_phase_duration;
+        self.launch_max_transaction_amount = launch_max_transaction_amount;
+        self.launch_max_wallet_amount = launch_max_wallet_amount;
+        self.launch_transaction_cooldown = launch_transaction_cooldown;
+
+        emit!(LaunchLimitsUpdated {
+            launch_phase_duration,
+            launch_max_transaction_amount,
+            launch_max_wallet_amount,
+            launch_transaction_cooldown,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Shared by `update_anti_sniper` and governance execution
+    pub fn apply_anti_sniper(
+        &mut self,
+        anti_sniper_slots: u64,
+        anti_sniper_fee_bp: u16,
+        anti_sniper_block: bool,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            anti_sniper_slots <= MAX_ANTI_SNIPER_SLOTS && anti_sniper_fee_bp <= MAX_ANTI_SNIPER_FEE_BP,
+            BigBallsError::InvalidAntiSniperConfiguration
+        );
+
+        self.anti_sniper_slots = anti_sniper_slots;
+        self.anti_sniper_fee_bp = anti_sniper_fee_bp;
+        self.anti_sniper_block = anti_sniper_block;
+
+        emit!(AntiSniperUpdated {
+            anti_sniper_slots,
+            anti_sniper_fee_bp,
+            anti_sniper_block,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Shared by `set_dev_wallet` and governance execution
+    pub fn apply_dev_wallet(&mut self, dev_wallet: Pubkey, now: i64) -> Result<()> {
+        require!(
+            dev_wallet != Pubkey::default(),
+            BigBallsError::InvalidDevWallet
+        );
+
+        let old_dev_wallet = self.dev_wallet;
+        self.dev_wallet = dev_wallet;
+
+        emit!(DevWalletUpdated {
+            old_dev_wallet,
+            new_dev_wallet: dev_wallet,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Shared by `add_amm_pool` and governance execution
+    pub fn apply_add_amm_pool(&mut self, pool: Pubkey, now: i64) -> Result<()> {
+        if !self.amm_pools.contains(&pool) {
+            require!(
+                self.amm_pools.len() < MAX_AMM_POOLS,
+                BigBallsError::AmmPoolListFull
+            );
+            self.amm_pools.push(pool);
+        }
+
+        emit!(AmmPoolUpdated {
+            pool,
+            registered: true,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Shared by `remove_amm_pool` and governance execution
+    pub fn apply_remove_amm_pool(&mut self, pool: Pubkey, now: i64) -> Result<()> {
+        let index = self.amm_pools
+            .iter()
+            .position(|registered| *registered == pool)
+            .ok_or(BigBallsError::AmmPoolNotFound)?;
+        self.amm_pools.remove(index);
+
+        emit!(AmmPoolUpdated {
+            pool,
+            registered: false,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Protocol wallets and registered pools are not subject to the max
+    /// wallet cap; other accounts (burn address, treasury) are exempted
+    /// through a limit-exempt `Exemption`
+    pub fn is_max_wallet_exempt(&self, account: &Pubkey) -> bool {
+        *account == self.marketing_wallet
+            || *account == self.dev_wallet
+            || self.amm_pools.contains(account)
+    }
+
+    /// Vote and treasury vaults pool many holders' tokens, so the hook keeps
+    /// them out of the wallet cap; their owners are program PDAs with no
+    /// exemption of their own. Addresses are rebuilt from the recorded bumps.
+    pub fn is_pooled_vault(&self, config: &Pubkey, account: &Pubkey) -> bool {
+        [(&b"vote_vault"[..], self.vote_vault_bump), (&b"treasury_vault"[..], self.treasury_vault_bump)]
+            .into_iter()
+            .filter_map(|(seed, bump)| Some((seed, bump?)))
+            .any(|(seed, bump)| {
+                Pubkey::create_program_address(&[seed, config.as_ref(), &[bump]], &crate::ID) == Ok(*account)
+            })
+    }
+
+    /// Tokens leaving a registered pool are buys, tokens entering one are sells
+    pub fn transfer_direction(&self, from: &Pubkey, to: &Pubkey) -> TransferDirection {
+        if self.amm_pools.contains(from) {
+            TransferDirection::Buy
+        } else if self.amm_pools.contains(to) {
+            TransferDirection::Sell
+        } else {
+            TransferDirection::WalletToWallet
+        }
+    }
+
+    /// Cooldown period (in seconds) applied to a transfer in the given direction
+    /// Buy and sell cooldowns apply in every phase; only the wallet-to-wallet
+    /// cooldown follows the launch schedule
+    pub fn cooldown_for(&self, direction: TransferDirection, limits: &PhaseLimits) -> i64 {
+        match direction {
+            TransferDirection::Buy => self.buy_cooldown,
+            TransferDirection::Sell => self.sell_cooldown,
+            TransferDirection::WalletToWallet => limits.transaction_cooldown,
+        }
+    }
+
+    /// Whether `now` falls inside the launch phase that follows trading enablement
+    pub fn in_launch_phase(&self, now: i64) -> bool {
+        self.trading_enabled_at > 0 && now < self.trading_enabled_at + self.launch_phase_duration
+    }
+
+    /// Limits in force at `now`: anti-bot launch limits first, post-launch limits after
+    pub fn active_limits(&self, now: i64) -> PhaseLimits {
+        if self.in_launch_phase(now) {
+            PhaseLimits {
+                max_transaction_amount: self.launch_max_transaction_amount,
+                max_wallet_amount: self.launch_max_wallet_amount,
+                transaction_cooldown: self.launch_transaction_cooldown,
+            }
+        } else {
+            PhaseLimits {
+                max_transaction_amount: self.max_transaction_amount,
+                max_wallet_amount: self.max_wallet_amount,
+                transaction_cooldown: self.transaction_cooldown,
+            }
+        }
+    }
+
+    /// Supply still in circulation after auto-burns
+    pub fn circulating_supply(&self) -> u64 {
+        self.total_supply.saturating_sub(self.total_burned)
+    }
+
+    /// Track the burn share of a fee split in the cumulative burned supply
+    pub fn record_burn(&mut self, amount: u64, from: Pubkey, now: i64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        self.total_burned = self.total_burned
+            .checked_add(amount)
+            .ok_or(BigBallsError::InvalidFeeCalculation)?;
+
+        emit!(TokensBurned {
+            mint: self.mint,
+            from,
+            amount,
+            total_burned: self.total_burned,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Spread the reflection share of a fee split across circulating supply
+    pub fn add_reflections(&mut self, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let circulating_supply = self.circulating_supply();
+        if circulating_supply > 0 {
+            self.reflection_per_token = self.reflection_per_token
+                .checked_add(amount as u128 * REFLECTION_PRECISION / circulating_supply as u128)
+                .ok_or(BigBallsError::InvalidFeeCalculation)?;
+        }
+        self.total_reflections = self.total_reflections
+            .checked_add(amount)
+            .ok_or(BigBallsError::InvalidFeeCalculation)?;
+        Ok(())
+    }
+}
+
+impl HolderState {
+    pub const LEN: usize = 8 +  // last_transaction
+                          8 +  // total_transactions
+                          8 +  // total_amount
+                          8 +  // last_reflection_claim
+                          8 +  // last_buy
+                          8 +  // last_sell
+                          VolumeWindow::LEN + // buy_volume
+                          VolumeWindow::LEN + // sell_volume
+                          16 + // reflection_checkpoint
+                          8 +  // checkpoint_balance
+                          8 +  // pending_reflections
+                          1 +  // initialized
+                          8 +  // escrowed_amount
+                          8;   // last_transfer_slot
+
+    /// Accrue reflections earned since the last checkpoint, then checkpoint
+    /// `balance`. Only tokens held continuously since the last checkpoint earn:
+    /// tokens that arrived without passing through the program (so were never
+    /// checkpointed) start earning from this settlement. A fresh holder state
+    /// only records the checkpoint so it cannot claim earlier reflections.
+    pub fn settle_reflections(&mut self, balance: u64, reflection_per_token: u128) -> Result<()> {
+        if self.initialized {
+            let eligible = balance
+                .min(self.checkpoint_balance)
+                .saturating_add(self.escrowed_amount);
+            let earned = (eligible as u128)
+                .checked_mul(reflection_per_token.saturating_sub(self.reflection_checkpoint))
+                .ok_or(BigBallsError::InvalidFeeCalculation)?
+                / REFLECTION_PRECISION;
+            self.pending_reflections = self.pending_reflections
+                .checked_add(earned as u64)
+                .ok_or(BigBallsError::InvalidFeeCalculation)?;
+        }
+        self.reflection_checkpoint = reflection_per_token;
+        self.checkpoint_balance = balance;
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Add a buy to the rolling 24h window if it stays within `limit`
+    pub fn record_buy(&mut self, amount: u64, now: i64, limit: u64) -> Result<()> {
+        require!(
+            self.buy_volume.volume_with(amount, now)? <= limit,
+            BigBallsError::ExceedsDailyBuyLimit
+        );
+        self.buy_volume.add(amount, now);
+        Ok(())
+    }
+
+    /// Add a sell to the rolling 24h window if it stays within `limit`
+    pub fn record_sell(&mut self, amount: u64, now: i64, limit: u64) -> Result<()> {
+        require!(
+            self.sell_volume.volume_with(amount, now)? <= limit,
+            BigBallsError::ExceedsDailySellLimit
+        );
+        self.sell_volume.add(amount, now);
+        Ok(())
+    }
+}
+
+/// Trailing volume in hourly buckets. Summing the current hour with the 24
+/// before it covers at least the last 24 hours, so no 24h span can exceed
+/// the cap by straddling a window boundary.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VolumeWindow {
+    pub last_hour: i64,
+    pub buckets: [u64; VOLUME_WINDOW_BUCKETS],
+}
+
+impl VolumeWindow {
+    pub const LEN: usize = 8 + 8 * VOLUME_WINDOW_BUCKETS;
+
+    /// Window volume as of `now`, including `amount`
+    pub fn volume_with(&self, amount: u64, now: i64) -> Result<u64> {
+        let mut window = *self;
+        window.rotate(now);
+        window.buckets
+            .iter()
+            .try_fold(amount, |total, volume| total.checked_add(*volume))
+            .ok_or(BigBallsError::InvalidAmount.into())
+    }
+
+    pub fn add(&mut self, amount: u64, now: i64) {
+        self.rotate(now);
+        let bucket = &mut self.buckets[self.last_hour as usize % VOLUME_WINDOW_BUCKETS];
+        *bucket = bucket.saturating_add(amount);
+    }
+
+    // Clear the buckets of hours that have rolled out of the window
+    fn rotate(&mut self, now: i64) {
+        let hour = now / SECONDS_PER_HOUR;
+        if hour <= self.last_hour {
+            return;
+        }
+        if hour - self.last_hour >= VOLUME_WINDOW_BUCKETS as i64 {
+            self.buckets = [0; VOLUME_WINDOW_BUCKETS];
+        } else {
+            for expired in self.last_hour + 1..=hour {
+                self.buckets[expired as usize % VOLUME_WINDOW_BUCKETS] = 0;
+            }
+        }
+        self.last_hour = hour;
+    }
+}
+
+impl Exemption {
+    pub const LEN: usize = 32 + // owner
+                          1 +  // fee_exempt
+                          1 +  // limit_exempt
+                          1 +  // cooldown_exempt
+                          1;   // sniper_whitelisted
+
+    pub fn flags(&self) -> ExemptionFlags {
+        ExemptionFlags {
+            fee_exempt: self.fee_exempt,
+            limit_exempt: self.limit_exempt,
+            cooldown_exempt: self.cooldown_exempt,
+            sniper_whitelisted: self.sniper_whitelisted,
+        }
+    }
+
+    /// Shared by `set_exemption` and governance execution
+    pub fn apply(&mut self, owner: Pubkey, flags: ExemptionFlags, now: i64) {
+        self.owner = owner;
+        self.fee_exempt = flags.fee_exempt;
+        self.limit_exempt = flags.limit_exempt;
+        self.cooldown_exempt = flags.cooldown_exempt;
+        self.sniper_whitelisted = flags.sniper_whitelisted;
+
+        emit!(ExemptionUpdated {
+            owner,
+            fee_exempt: flags.fee_exempt,
+            limit_exempt: flags.limit_exempt,
+            cooldown_exempt: flags.cooldown_exempt,
+            sniper_whitelisted: flags.sniper_whitelisted,
+            timestamp: now,
+        });
+    }
+}
+
+impl VestingSchedule {
+    pub const LEN: usize = 32 + // config
+                          32 + // beneficiary
+                          32 + // vault
+                          8 +  // total_amount
+                          8 +  // released_amount
+                          8 +  // start_time
+                          8 +  // cliff_duration
+                          8;   // vesting_duration
+
+    /// Total amount vested at `now`, including anything already released
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.start_time);
+        if elapsed < self.cliff_duration {
+            0
+        } else if elapsed >= self.vesting_duration {
+            self.total_amount
+        } else {
+            (self.total_amount as u128 * elapsed as u128 / self.vesting_duration as u128) as u64
+        }
+    }
+
+    pub fn releasable_amount(&self, now: i64) -> u64 {
+        self.vested_amount(now).saturating_sub(self.released_amount)
+    }
+}
+
+impl LockVault {
+    pub const LEN: usize = 32 + // owner
+                          32 + // mint
+                          32 + // vault
+                          8 +  // lock_id
+                          8 +  // amount
+                          8 +  // locked_at
+                          8;   // unlock_time
+}
+
+impl MarketingMultisig {
+    pub const LEN: usize = 32 + // config
+                          32 + // vault
+                          4 + 32 * MAX_MARKETING_SIGNERS + // signers
+                          1 +  // threshold
+                          8 +  // timelock_threshold
+                          8 +  // timelock_duration
+                          8;   // proposal_count
+
+    pub fn is_signer(&self, key: &Pubkey) -> bool {
+        self.signers.contains(key)
+    }
+}
+
+impl MarketingProposal {
+    pub const LEN: usize = 32 + // multisig
+                          8 +  // index
+                          32 + // proposer
+                          MarketingAction::LEN + // action
+                          4 + 32 * MAX_MARKETING_SIGNERS + // approvals
+                          8 +  // created_at
+                          8 +  // executable_at
+                          1;   // executed
+
+    /// Approvals from signers still on the multisig
+    pub fn valid_approvals(&self, multisig: &MarketingMultisig) -> usize {
+        self.approvals.iter().filter(|signer| multisig.is_signer(signer)).count()
+    }
+
+    /// Record an approval; the timelock starts once the threshold is reached
+    pub fn approve(&mut self, multisig: &MarketingMultisig, signer: Pubkey, now: i64) -> Result<()> {
+        require!(!self.approvals.contains(&signer), BigBallsError::AlreadyApproved);
+        self.approvals.push(signer);
+
+        if self.executable_at == 0 && self.valid_approvals(multisig) >= multisig.threshold as usize {
+            self.executable_at = if self.action.is_timelocked(multisig) {
+                now + multisig.timelock_duration
+            } else {
+                now
+            };
+        }
+
+        emit!(MarketingProposalApproved {
+            index: self.index,
+            signer,
+            approvals: self.approvals.len() as u8,
+            executable_at: self.executable_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl Governance {
+    pub const LEN: usize = 32 + // config
+                          32 + // vote_vault
+                          8 +  // proposal_count
+                          8 +  // proposal_threshold
+                          8 +  // voting_period
+                          2 +  // quorum_bp
+                          2 +  // approval_bp
+                          8;   // execution_delay
+
+    /// Apply the governance parameters from the tokenomics spec
+    pub fn init_defaults(&mut self, config: &Account<TokenConfig>, vote_vault: Pubkey) {
+        self.config = config.key();
+        self.vote_vault = vote_vault;
+        self.proposal_count = 0;
+        self.proposal_threshold = config.total_supply / 1000; // 0.1% of supply
+        self.voting_period = 3 * SECONDS_PER_DAY;
+        self.quorum_bp = 1000; // 10% of circulating supply
+        self.approval_bp = 6600; // 66% of votes cast
+        self.execution_delay = SECONDS_PER_DAY;
+    }
+}
+
+impl Proposal {
+    pub const LEN: usize = 32 + // governance
+                          8 +  // index
+                          32 + // proposer
+                          ProposalAction::LEN + // action
+                          32 + // description_hash
+                          8 +  // votes_for
+                          8 +  // votes_against
+                          8 +  // created_at
+                          8 +  // voting_ends_at
+                          8 +  // executable_at
+                          1;   // status
+}
+
+impl VoteRecord {
+    pub const LEN: usize = 32 + // proposal
+                          32 + // voter
+                          8 +  // weight
+                          1;   // support
+}
+
+impl VoterRecord {
+    pub const LEN: usize = 32 + // voter
+                          32 + // token_account
+                          8 +  // voting_power
+                          8 +  // locked_until
+                          8;   // active_proposal_ends_at
+}
+
+impl Treasury {
+    pub const LEN: usize = 32 + // config
+                          32 + // vault
+                          8 +  // pending_burn_amount
+                          8 +  // burn_executable_at
+                          8;   // burn_count
+
+    /// A pending burn not executed within the expiry window is abandoned
+    pub fn is_burn_expired(&self, now: i64) -> bool {
+        self.pending_burn_amount > 0 && now > self.burn_executable_at + MANUAL_BURN_EXPIRY
+    }
+
+    /// Queue a burn of treasury tokens behind the notice period
+    pub fn schedule_burn(&mut self, amount: u64, config: &TokenConfig, now: i64) -> Result<()> {
+        require!(
+            self.pending_burn_amount == 0 || self.is_burn_expired(now),
+            BigBallsError::BurnAlreadyScheduled
+        );
+        require!(amount > 0, BigBallsError::InvalidAmount);
+        require!(
+            amount as u128 <= config.total_supply as u128 * MAX_MANUAL_BURN_BP as u128 / 10000,
+            BigBallsError::ExceedsManualBurnCap
+        );
+
+        self.pending_burn_amount = amount;
+        self.burn_executable_at = now + MANUAL_BURN_NOTICE;
+
+        emit!(ManualBurnScheduled {
+            index: self.burn_count + 1,
+            amount,
+            executable_at: self.burn_executable_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Drop the pending burn; only reachable through governance
+    pub fn cancel_burn(&mut self, now: i64) -> Result<()> {
+        let amount = self.pending_burn_amount;
+        require!(amount > 0, BigBallsError::NoBurnScheduled);
+
+        self.pending_burn_amount = 0;
+        self.burn_executable_at = 0;
+
+        emit!(ManualBurnCancelled {
+            index: self.burn_count + 1,
+            amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+impl PriceOracle {
+    pub const LEN: usize = 32 + // config
+                          32 + // pool_token_account
+                          32 + // pool_quote_account
+                          2 +  // drop_threshold_bp
+                          8 +  // window
+                          8 +  // pause_duration
+                          PriceObservation::LEN * PRICE_OBSERVATIONS + // observations
+                          1;   // next_index
+
+    pub fn last_observed_at(&self) -> i64 {
+        let last = (self.next_index as usize + PRICE_OBSERVATIONS - 1) % PRICE_OBSERVATIONS;
+        self.observations[last].timestamp
+    }
+
+    /// Recorded observations, oldest first
+    fn history(&self) -> Vec<PriceObservation> {
+        (0..PRICE_OBSERVATIONS)
+            .map(|i| self.observations[(self.next_index as usize + i) % PRICE_OBSERVATIONS])
+            .filter(|o| o.timestamp != 0)
+            .collect()
+    }
+
+    /// Highest price held across `CIRCUIT_BREAKER_CONFIRMATIONS` consecutive
+    /// observations at or after `since`, or zero without enough history
+    pub fn peak_price_since(&self, since: i64) -> u128 {
+        let history: Vec<u128> = self.history()
+            .iter()
+            .filter(|o| o.timestamp >= since)
+            .map(|o| o.price)
+            .collect();
+        history
+            .windows(CIRCUIT_BREAKER_CONFIRMATIONS)
+            .filter_map(|run| run.iter().min().copied())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Highest of the latest `CIRCUIT_BREAKER_CONFIRMATIONS` observations,
+    /// or None until that many have been recorded
+    pub fn recent_price(&self) -> Option<u128> {
+        let history = self.history();
+        let start = history.len().checked_sub(CIRCUIT_BREAKER_CONFIRMATIONS)?;
+        history[start..].iter().map(|o| o.price).max()
+    }
+
+    /// Whether `current` sits more than `drop_threshold_bp` below `reference`
+    pub fn is_drop(&self, reference: u128, current: u128) -> bool {
+        reference > 0 && current * 10000 < reference * (10000 - self.drop_threshold_bp as u128)
+    }
+
+    /// Overwrite the oldest observation
+    pub fn record(&mut self, timestamp: i64, price: u128) {
+        self.observations[self.next_index as usize] = PriceObservation { timestamp, price };
+        self.next_index = ((self.next_index as usize + 1) % PRICE_OBSERVATIONS) as u8;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PriceObservation {
+    pub timestamp: i64,
+    pub price: u128,
+}
+
+impl PriceObservation {
+    pub const LEN: usize = 8 + 16;
+}
+
+impl GuardianSet {
+    pub const LEN: usize = 32 + // config
+                          4 + 32 * MAX_GUARDIANS + // guardians
+                          1 +  // threshold
+                          8 +  // timelock_duration
+                          8;   // action_count
+
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        self.guardians.contains(key)
+    }
+}
+
+impl EmergencyAction {
+    pub const LEN: usize = 32 + // guardian_set
+                          8 +  // index
+                          1 +  // kind
+                          32 + // reason_hash
+                          4 + MAX_REASON_URI_LEN + // reason_uri
+                          32 + // proposer
+                          4 + 32 * MAX_GUARDIANS + // approvals
+                          8 +  // created_at
+                          8 +  // executable_at
+                          1;   // executed
+
+    /// An approved action left unexecuted past the expiry window is void
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.executable_at != 0 && now > self.executable_at + EMERGENCY_ACTION_EXPIRY
+    }
+
+    /// Record an approval; the timelock starts once the threshold is reached
+    pub fn approve(&mut self, guardian_set: &GuardianSet, guardian: Pubkey, now: i64) -> Result<()> {
+        require!(!self.approvals.contains(&guardian), BigBallsError::AlreadyApproved);
+        self.approvals.push(guardian);
+
+        if self.approvals.len() == guardian_set.threshold as usize {
+            self.executable_at = now + guardian_set.timelock_duration;
+        }
+
+        emit!(EmergencyActionApproved {
+            index: self.index,
+            guardian,
+            approvals: self.approvals.len() as u8,
+            executable_at: self.executable_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmergencyActionKind {
+    Shutdown,
+    Resume,
+}
+
+impl Blocklist {
+    pub const LEN: usize = 32 + // address
+                          1 +  // blocked
+                          1 +  // reason
+                          8;   // updated_at
+
+    /// Shared by `block_address` and governance execution
+    pub fn apply_block(&mut self, address: Pubkey, reason: u8, now: i64) {
+        self.address = address;
+        self.blocked = true;
+        self.reason = reason;
+        self.updated_at = now;
+
+        emit!(AddressBlocked {
+            address,
+            reason,
+            timestamp: now,
+        });
+    }
+
+    /// Shared by `unblock_address` and governance execution
+    pub fn apply_unblock(&mut self, now: i64) {
+        self.blocked = false;
+        self.updated_at = now;
+
+        emit!(AddressUnblocked {
+            address: self.address,
+            timestamp: now,
+        });
+    }
+}
+
+/// Actions the marketing multisig can approve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum MarketingAction {
+    Spend {
+        recipient: Pubkey,
+        amount: u64,
+    },
+    UpdateTimelock {
+        timelock_threshold: u64,
+        timelock_duration: i64,
+    },
+    RotateSigners {
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+}
+
+impl MarketingAction {
+    // variant tag + largest payload (RotateSigners)
+    pub const LEN: usize = 1 + 4 + 32 * MAX_MARKETING_SIGNERS + 1;
+
+    pub fn validate(&self, config: &TokenConfig) -> Result<()> {
+        match self {
+            MarketingAction::Spend { amount, .. } => {
+                require!(*amount > 0, BigBallsError::InvalidAmount);
+            }
+            MarketingAction::UpdateTimelock { timelock_threshold, timelock_duration } => {
+                require!(
+                    *timelock_duration >= MIN_MARKETING_TIMELOCK
+                        && *timelock_threshold <= config.total_supply / 100, // 1% of supply
+                    BigBallsError::InvalidTimelock
+                );
+            }
+            MarketingAction::RotateSigners { signers, threshold } => {
+                validate_signer_set(signers, *threshold, MAX_MARKETING_SIGNERS)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Spends at or below the threshold run on approval; everything else waits
+    pub fn is_timelocked(&self, multisig: &MarketingMultisig) -> bool {
+        match self {
+            MarketingAction::Spend { amount, .. } => *amount > multisig.timelock_threshold,
+            _ => true,
+        }
+    }
+}
+
+/// Admin actions a passed proposal can execute through the governance PDA
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalAction {
+    AcceptAuthority,
+    UpdateFees {
+        reflection_fee_bp: u16,
+        marketing_fee_bp: u16,
+        burn_fee_bp: u16,
+        dev_fee_bp: u16,
+    },
+    SetTradingEnabled {
+        enabled: bool,
+    },
+    SetPauseFlags {
+        flags: u8,
+    },
+    BlockAddress {
+        address: Pubkey,
+        reason: u8,
+    },
+    UnblockAddress {
+        address: Pubkey,
+    },
+    ScheduleBurn {
+        amount: u64,
+    },
+    CancelBurn,
+    ProposeAuthority {
+        new_authority: Pubkey,
+    },
+    UpdateLimits {
+        max_transaction_amount: u64,
+        max_wallet_amount: u64,
+        buy_cooldown: i64,
+        sell_cooldown: i64,
+        transaction_cooldown: i64,
+    },
+    UpdateLaunchLimits {
+        launch_phase_duration: i64,
+        launch_max_transaction_amount: u64,
+        launch_max_wallet_amount: u64,
+        launch_transaction_cooldown: i64,
+    },
+    UpdateAntiSniper {
+        anti_sniper_slots: u64,
+        anti_sniper_fee_bp: u16,
+        anti_sniper_block: bool,
+    },
+    SetDevWallet {
+        dev_wallet: Pubkey,
+    },
+    AddAmmPool {
+        pool: Pubkey,
+    },
+    RemoveAmmPool {
+        pool: Pubkey,
+    },
+    SetExemption {
+        owner: Pubkey,
+        fee_exempt: bool,
+        limit_exempt: bool,
+        cooldown_exempt: bool,
+        sniper_whitelisted: bool,
+    },
+}
+
+impl ProposalAction {
+    pub const LEN: usize = 1 + 40; // variant tag + largest payload (UpdateLimits)
+
+    /// Whether executing this action re-opens a paused governance
+    pub fn lifts_governance_pause(&self) -> bool {
+        matches!(self, ProposalAction::SetPauseFlags { flags } if flags & PAUSE_GOVERNANCE == 0)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Active,
+    Succeeded,
+    Defeated,
+    Executed,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExemptionFlags {
+    pub fee_exempt: bool,
+    pub limit_exempt: bool,
+    pub cooldown_exempt: bool,
+    pub sniper_whitelisted: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Allocation {
+    pub destination: Pubkey,
+    pub basis_points: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PhaseLimits {
+    pub max_transaction_amount: u64,
+    pub max_wallet_amount: u64,
+    pub transaction_cooldown: i64,
 }
 
-#[account]
-pub struct HolderState {
-    pub last_transaction: i64,
-    pub total_transactions: u64,
-    pub total_amount: u64,
-    pub last_reflection_claim: i64,
-}
-
-impl TokenConfig {
-    pub const LEN: usize = 32 + // name
-                          32 + // symbol
-                          1 +  // decimals
-                          8 +  // total_supply
-                          1 +  // trading_enabled
-                          32 + // authority
-                          32 + // marketing_wallet
-                          8 +  // max_transaction_amount
-                          8 +  // max_wallet_amount
-                          2 +  // reflection_fee_bp
-                          2 +  // marketing_fee_bp
-                          2 +  // burn_fee_bp
-                          2 +  // dev_fee_bp
-                          8 +  // buy_cooldown
-                          8 +  // sell_cooldown
-                          8;   // transaction_cooldown
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferDirection {
+    Buy,
+    Sell,
+    WalletToWallet,
 }
 
-impl HolderState {
-    pub const LEN: usize = 8 +  // last_transaction
-                          8 +  // total_transactions
-                          8 +  // total_amount
-                          8;   // last_reflection_claim
+impl TransferDirection {
+    pub fn pause_flag(&self) -> u8 {
+        match self {
+            TransferDirection::Buy => PAUSE_BUYS,
+            TransferDirection::Sell => PAUSE_SELLS,
+            TransferDirection::WalletToWallet => PAUSE_TRANSFERS,
+        }
+    }
 }
 
 #[event]
@@ -329,6 +5169,52 @@ pub struct TokenInitialized {
     pub total_supply: u64,
     pub decimals: u8,
     pub marketing_wallet: Pubkey,
+    pub dev_wallet: Pubkey,
+}
+
+#[event]
+pub struct TransferExtensionsInitialized {
+    pub mint: Pubkey,
+    pub transfer_fee_bp: u16,
+    pub fee_authority: Pubkey,
+    pub transfer_hook_program: Pubkey,
+}
+
+#[event]
+pub struct ExtraAccountMetaListInitialized {
+    pub mint: Pubkey,
+    pub extra_account_meta_list: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExtraAccountMetaListUpdated {
+    pub mint: Pubkey,
+    pub extra_account_meta_list: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InitialSupplyMinted {
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllocationDistributed {
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub basis_points: u16,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintAuthorityRevoked {
+    pub mint: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
@@ -336,6 +5222,7 @@ pub struct TransferExecuted {
     pub from: Pubkey,
     pub to: Pubkey,
     pub amount: u64,
+    pub direction: TransferDirection,
     pub timestamp: i64,
 }
 
@@ -348,6 +5235,375 @@ pub struct FeesCollected {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct TokensBurned {
+    pub mint: Pubkey,
+    pub from: Pubkey,
+    pub amount: u64,
+    pub total_burned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityRenounced {
+    pub old_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LimitsUpdated {
+    pub max_transaction_amount: u64,
+    pub max_wallet_amount: u64,
+    pub buy_cooldown: i64,
+    pub sell_cooldown: i64,
+    pub transaction_cooldown: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LaunchLimitsUpdated {
+    pub launch_phase_duration: i64,
+    pub launch_max_transaction_amount: u64,
+    pub launch_max_wallet_amount: u64,
+    pub launch_transaction_cooldown: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DevWalletUpdated {
+    pub old_dev_wallet: Pubkey,
+    pub new_dev_wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AmmPoolUpdated {
+    pub pool: Pubkey,
+    pub registered: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExemptionUpdated {
+    pub owner: Pubkey,
+    pub fee_exempt: bool,
+    pub limit_exempt: bool,
+    pub cooldown_exempt: bool,
+    pub sniper_whitelisted: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReflectionPoolInitialized {
+    pub reflection_pool: Pubkey,
+    pub pool_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReflectionsClaimed {
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingCreated {
+    pub vesting_schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+}
+
+#[event]
+pub struct VestedTokensReleased {
+    pub vesting_schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub total_released: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensLocked {
+    pub lock_vault: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub unlock_time: i64,
+}
+
+#[event]
+pub struct LockExtended {
+    pub lock_vault: Pubkey,
+    pub old_unlock_time: i64,
+    pub new_unlock_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensUnlocked {
+    pub lock_vault: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketingVaultInitialized {
+    pub marketing_vault: Pubkey,
+    pub old_marketing_wallet: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketingTimelockUpdated {
+    pub timelock_threshold: u64,
+    pub timelock_duration: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketingSignersRotated {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketingProposalCreated {
+    pub marketing_proposal: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: MarketingAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketingProposalApproved {
+    pub index: u64,
+    pub signer: Pubkey,
+    pub approvals: u8,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketingProposalExecuted {
+    pub marketing_proposal: Pubkey,
+    pub index: u64,
+    pub executor: Pubkey,
+    pub action: MarketingAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceInitialized {
+    pub governance: Pubkey,
+    pub vote_vault: Pubkey,
+    pub proposal_threshold: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub description_hash: [u8; 32],
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalFinalized {
+    pub proposal: Pubkey,
+    pub status: ProposalStatus,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub action: ProposalAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VotesDeposited {
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub voting_power: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VotesWithdrawn {
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub voting_power: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryInitialized {
+    pub treasury: Pubkey,
+    pub treasury_vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ManualBurnScheduled {
+    pub index: u64,
+    pub amount: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ManualBurnCancelled {
+    pub index: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ManualBurnExecuted {
+    pub index: u64,
+    pub amount: u64,
+    pub total_burned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceOracleInitialized {
+    pub pool_token_account: Pubkey,
+    pub pool_quote_account: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerUpdated {
+    pub drop_threshold_bp: u16,
+    pub window: i64,
+    pub pause_duration: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerTriggered {
+    pub reason: u8,
+    pub reference_price: u128,
+    pub current_price: u128,
+    pub paused_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardianSetInitialized {
+    pub guardian_set: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timelock_duration: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyActionProposed {
+    pub emergency_action: Pubkey,
+    pub index: u64,
+    pub kind: EmergencyActionKind,
+    pub proposer: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub reason_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyActionApproved {
+    pub index: u64,
+    pub guardian: Pubkey,
+    pub approvals: u8,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyActionExecuted {
+    pub emergency_action: Pubkey,
+    pub index: u64,
+    pub kind: EmergencyActionKind,
+    pub executor: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub old_flags: u8,
+    pub new_flags: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddressBlocked {
+    pub address: Pubkey,
+    pub reason: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddressUnblocked {
+    pub address: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AntiSniperUpdated {
+    pub anti_sniper_slots: u64,
+    pub anti_sniper_fee_bp: u16,
+    pub anti_sniper_block: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SniperPenaltyApplied {
+    pub buyer: Pubkey,
+    pub penalty: u64,
+    pub slot: u64,
+}
+
 #[event]
 pub struct TradingStatusChanged {
     pub enabled: bool,
@@ -365,6 +5621,9 @@ pub struct FeesUpdated {
 
 #[error_code]
 pub enum BigBallsError {
+    // Deprecated: no longer returned since pause flags replaced the trading
+    // switch (paused actions fail with ActionPaused). Kept so later error codes
+    // stay stable for clients.
     #[msg("Trading is not enabled")]
     TradingNotEnabled,
     #[msg("Transaction amount exceeds limit")]
@@ -381,6 +5640,124 @@ pub enum BigBallsError {
     Unauthorized,
     #[msg("Invalid marketing wallet address")]
     InvalidMarketingWallet,
+    #[msg("Invalid dev wallet address")]
+    InvalidDevWallet,
     #[msg("Invalid fee configuration")]
     InvalidFeeConfiguration,
+    #[msg("Recipient must differ from sender")]
+    InvalidRecipient,
+    #[msg("AMM pool registry is full")]
+    AmmPoolListFull,
+    #[msg("AMM pool is not registered")]
+    AmmPoolNotFound,
+    #[msg("Daily buy limit exceeded")]
+    ExceedsDailyBuyLimit,
+    #[msg("Daily sell limit exceeded")]
+    ExceedsDailySellLimit,
+    #[msg("Invalid limit configuration")]
+    InvalidLimitConfiguration,
+    #[msg("Invalid authority address")]
+    InvalidAuthority,
+    #[msg("No pending authority to accept")]
+    NoPendingAuthority,
+    #[msg("Authority has been renounced")]
+    AuthorityRenounced,
+    #[msg("Transfer hook invoked outside of a Token-2022 transfer")]
+    NotTransferring,
+    #[msg("Initial supply has already been minted")]
+    InitialSupplyAlreadyMinted,
+    #[msg("Initial supply has not been minted")]
+    InitialSupplyNotMinted,
+    #[msg("Invalid allocation configuration")]
+    InvalidAllocation,
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    #[msg("No vested tokens available to release")]
+    NothingToRelease,
+    #[msg("Unlock time must be later than the current lock")]
+    InvalidUnlockTime,
+    #[msg("Lock has not expired")]
+    LockNotExpired,
+    #[msg("Invalid multisig signer set")]
+    InvalidSignerSet,
+    #[msg("Signer is not a member of the multisig")]
+    NotMultisigSigner,
+    #[msg("Signer has already approved")]
+    AlreadyApproved,
+    #[msg("Not enough approvals")]
+    InsufficientApprovals,
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Invalid timelock")]
+    InvalidTimelock,
+    #[msg("Marketing proposal has already been executed")]
+    SpendAlreadyExecuted,
+    #[msg("Escrowed votes below the proposal threshold")]
+    InsufficientProposalBalance,
+    #[msg("Voting is closed for this proposal")]
+    VotingClosed,
+    #[msg("Voting period has not ended")]
+    VotingStillOpen,
+    #[msg("Proposal is not active")]
+    ProposalNotActive,
+    #[msg("Proposal has not succeeded")]
+    ProposalNotSucceeded,
+    #[msg("No escrowed voting power")]
+    NoVotingPower,
+    #[msg("Escrowed votes are locked until voted proposals close")]
+    VotesLocked,
+    #[msg("Voter token account does not match the escrow record")]
+    InvalidVoterTokenAccount,
+    #[msg("Treasury account required for this action")]
+    TreasuryRequired,
+    #[msg("A manual burn is already scheduled")]
+    BurnAlreadyScheduled,
+    #[msg("No manual burn is scheduled")]
+    NoBurnScheduled,
+    #[msg("Manual burn exceeds the per-event cap")]
+    ExceedsManualBurnCap,
+    #[msg("Trading is paused by the circuit breaker")]
+    TradingPaused,
+    #[msg("Invalid circuit breaker configuration")]
+    InvalidCircuitBreakerConfiguration,
+    #[msg("Pool reserves are empty")]
+    InvalidPoolReserves,
+    #[msg("Price observed too recently")]
+    ObservationTooSoon,
+    #[msg("Token is in emergency shutdown")]
+    EmergencyShutdown,
+    #[msg("Signer is not a guardian")]
+    NotGuardian,
+    #[msg("Reason URI is too long")]
+    ReasonUriTooLong,
+    #[msg("Emergency action has already been executed")]
+    EmergencyActionAlreadyExecuted,
+    #[msg("This action is paused")]
+    ActionPaused,
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    #[msg("Address is blocked")]
+    AddressBlocked,
+    #[msg("Blocklist entry required for this action")]
+    BlocklistEntryRequired,
+    #[msg("Buys are blocked during the anti-sniper window")]
+    SniperBlocked,
+    #[msg("Only one transfer per slot is allowed")]
+    OneTransferPerSlot,
+    #[msg("Invalid anti-sniper configuration")]
+    InvalidAntiSniperConfiguration,
+    #[msg("Sender must initialize its holder state before transferring")]
+    HolderStateRequired,
+    #[msg("No withheld transfer fees to distribute")]
+    NoWithheldFees,
+    #[msg("Token-2022 fee updates require the mint, fee authority and token program")]
+    TransferFeeAccountsRequired,
+    #[msg("Exemption entry required for this action")]
+    ExemptionRequired,
+    #[msg("Escrowed votes already back an open proposal")]
+    ProposalAlreadyActive,
+    #[msg("Scheduled burn expired before execution")]
+    BurnExpired,
+    #[msg("Emergency action expired before execution")]
+    EmergencyActionExpired,
 }
@@ -1,12 +1,23 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::clock::Clock;
 use solana_program_test::*;
+use anchor_lang::solana_program::{program_pack::Pack, system_instruction, sysvar};
+use anchor_lang::{system_program, InstructionData};
 use solana_sdk::{
+    instruction::InstructionError,
     signature::{Keypair, Signature},
     signer::Signer,
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
     commitment_config::CommitmentConfig,
 };
+use spl_token_2022::extension::{
+    transfer_fee::TransferFeeConfig,
+    transfer_hook::TransferHook,
+    BaseStateWithExtensions,
+    ExtensionType,
+    StateWithExtensions,
+};
+use spl_transfer_hook_interface::instruction::TransferHookInstruction;
 
 #[cfg(test)]
 mod tests {
@@ -22,11 +33,13 @@ mod tests {
             total_supply: u64,
             decimals: u8,
             marketing_wallet: Pubkey,
+            dev_wallet: Pubkey,
         },
         TransferExecuted {
             from: Pubkey,
             to: Pubkey,
             amount: u64,
+            direction: TransferDirection,
             timestamp: i64,
         },
         FeesCollected {
@@ -36,6 +49,13 @@ mod tests {
             dev_amount: u64,
             timestamp: i64,
         },
+        TokensBurned {
+            mint: Pubkey,
+            from: Pubkey,
+            amount: u64,
+            total_burned: u64,
+            timestamp: i64,
+        },
         TradingStatusChanged {
             enabled: bool,
             timestamp: i64,
@@ -94,12 +114,512 @@ mod tests {
     }
 
     // Test utilities
+    fn mint_authority_address(config: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"mint_authority", config.as_ref()],
+            &crate::ID,
+        ).0
+    }
+
     fn create_program_test() -> ProgramTest {
-        ProgramTest::new(
+        let mut program_test = ProgramTest::new(
             "bigballs_token",
             crate::ID,
             processor!(crate::entry),
-        )
+        );
+        // The bundled Token-2022 build predates transfer hooks
+        program_test.add_program(
+            "spl_token_2022",
+            spl_token_2022::ID,
+            processor!(spl_token_2022::processor::Processor::process),
+        );
+        program_test
+    }
+
+    fn pda(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &crate::ID).0
+    }
+
+    fn program_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: accounts.to_account_metas(None),
+            data: data.data(),
+        }
+    }
+
+    // Assert a transaction failed with a specific program or Anchor error code
+    fn assert_custom_error(result: Result<(), BanksClientError>, expected: impl Into<u32>) {
+        match result.expect_err("transaction should have failed").unwrap() {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+                assert_eq!(code, expected.into())
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    // Shared fixture: an initialized config with real fee wallets, on either
+    // the legacy token program or Token-2022
+    struct TestToken {
+        context: ProgramTestContext,
+        authority: Keypair,
+        config: Pubkey,
+        mint: Pubkey,
+        marketing_wallet: Pubkey,
+        dev_wallet: Pubkey,
+        token_program: Pubkey,
+    }
+
+    impl TestToken {
+        async fn new() -> Self {
+            Self::with_token_program(spl_token::ID).await
+        }
+
+        // Token-2022 mint with the transfer fee and hook extensions, and the
+        // hook's extra account meta list in place
+        async fn new_token_2022() -> Self {
+            Self::with_token_program(spl_token_2022::ID).await
+        }
+
+        async fn with_token_program(token_program: Pubkey) -> Self {
+            let context = create_program_test().start_with_context().await;
+            let config = Keypair::new();
+            let mint = Keypair::new();
+            let marketing_wallet = Keypair::new();
+            let dev_wallet = Keypair::new();
+
+            let mut token = TestToken {
+                context,
+                authority: Keypair::new(),
+                config: config.pubkey(),
+                mint: mint.pubkey(),
+                marketing_wallet: marketing_wallet.pubkey(),
+                dev_wallet: dev_wallet.pubkey(),
+                token_program,
+            };
+
+            let payer = token.context.payer.pubkey();
+            let rent = token.context.banks_client.get_rent().await.unwrap();
+            let fund_ix = system_instruction::transfer(&payer, &token.authority.pubkey(), 10_000_000_000);
+            let instructions = if token_program == spl_token::ID {
+                vec![
+                    fund_ix,
+                    system_instruction::create_account(
+                        &payer,
+                        &token.mint,
+                        rent.minimum_balance(spl_token::state::Mint::LEN),
+                        spl_token::state::Mint::LEN as u64,
+                        &spl_token::ID,
+                    ),
+                    program_ix(
+                        crate::accounts::Initialize {
+                            config: token.config,
+                            mint: token.mint,
+                            mint_authority: mint_authority_address(token.config),
+                            authority: token.authority.pubkey(),
+                            token_program: spl_token::ID,
+                            system_program: system_program::ID,
+                            rent: sysvar::rent::ID,
+                        },
+                        crate::instruction::Initialize {
+                            name: "Big Balls".to_string(),
+                            symbol: "BIGBALLS".to_string(),
+                            decimals: 9,
+                            marketing_wallet: token.marketing_wallet,
+                            dev_wallet: token.dev_wallet,
+                        },
+                    ),
+                ]
+            } else {
+                vec![
+                    fund_ix,
+                    program_ix(
+                        crate::accounts::InitializeToken2022 {
+                            config: token.config,
+                            mint: token.mint,
+                            fee_authority: pda(&[b"fee_authority", token.config.as_ref()]),
+                            mint_authority: mint_authority_address(token.config),
+                            authority: token.authority.pubkey(),
+                            token_program: spl_token_2022::ID,
+                            system_program: system_program::ID,
+                        },
+                        crate::instruction::InitializeToken2022 {
+                            name: "Big Balls".to_string(),
+                            symbol: "BIGBALLS".to_string(),
+                            decimals: 9,
+                            marketing_wallet: token.marketing_wallet,
+                            dev_wallet: token.dev_wallet,
+                        },
+                    ),
+                    program_ix(
+                        crate::accounts::InitializeExtraAccountMetaList {
+                            config: token.config,
+                            mint: token.mint,
+                            extra_account_meta_list: pda(&[b"extra-account-metas", token.mint.as_ref()]),
+                            authority: token.authority.pubkey(),
+                            system_program: system_program::ID,
+                        },
+                        crate::instruction::InitializeExtraAccountMetaList {},
+                    ),
+                ]
+            };
+            token.send_as_authority(&instructions, &[&config, &mint]).await.unwrap();
+
+            // Fee wallets are token accounts owned by throwaway wallets
+            token.create_token_account_at(&marketing_wallet, &Pubkey::new_unique()).await;
+            token.create_token_account_at(&dev_wallet, &Pubkey::new_unique()).await;
+
+            token
+        }
+
+        async fn send(
+            &mut self,
+            instructions: &[Instruction],
+            signers: &[&Keypair],
+        ) -> Result<(), BanksClientError> {
+            let recent_blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+            let mut all_signers = vec![&self.context.payer];
+            all_signers.extend_from_slice(signers);
+            let transaction = Transaction::new_signed_with_payer(
+                instructions,
+                Some(&self.context.payer.pubkey()),
+                &all_signers,
+                recent_blockhash,
+            );
+            self.context.banks_client.process_transaction(transaction).await
+        }
+
+        async fn send_as_authority(
+            &mut self,
+            instructions: &[Instruction],
+            signers: &[&Keypair],
+        ) -> Result<(), BanksClientError> {
+            let authority = self.authority.insecure_clone();
+            let mut all_signers = vec![&authority];
+            all_signers.extend_from_slice(signers);
+            self.send(instructions, &all_signers).await
+        }
+
+        async fn create_token_account(&mut self, owner: &Pubkey) -> Pubkey {
+            let account = Keypair::new();
+            self.create_token_account_at(&account, owner).await;
+            account.pubkey()
+        }
+
+        async fn create_token_account_at(&mut self, account: &Keypair, owner: &Pubkey) {
+            let payer = self.context.payer.pubkey();
+            let rent = self.context.banks_client.get_rent().await.unwrap();
+
+            // Token-2022 accounts need room for the extensions the mint requires
+            let mint_account = self.context.banks_client.get_account(self.mint).await.unwrap().unwrap();
+            let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_account.data).unwrap();
+            let account_extensions = ExtensionType::get_required_init_account_extensions(
+                &mint_state.get_extension_types().unwrap(),
+            );
+            let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
+                &account_extensions,
+            ).unwrap();
+
+            let instructions = [
+                system_instruction::create_account(
+                    &payer,
+                    &account.pubkey(),
+                    rent.minimum_balance(space),
+                    space as u64,
+                    &self.token_program,
+                ),
+                spl_token_2022::instruction::initialize_account3(
+                    &self.token_program,
+                    &account.pubkey(),
+                    &self.mint,
+                    owner,
+                ).unwrap(),
+            ];
+            self.send(&instructions, &[account]).await.unwrap();
+        }
+
+        // Mint the whole supply into an authority-owned token account
+        async fn mint_supply(&mut self) -> Pubkey {
+            let destination = self.create_token_account(&self.authority.pubkey()).await;
+            let mint_ix = program_ix(
+                crate::accounts::MintInitialSupply {
+                    config: self.config,
+                    mint: self.mint,
+                    mint_authority: mint_authority_address(self.config),
+                    destination,
+                    authority: self.authority.pubkey(),
+                    token_program: self.token_program,
+                },
+                crate::instruction::MintInitialSupply {},
+            );
+            self.send_as_authority(&[mint_ix], &[]).await.unwrap();
+            destination
+        }
+
+        async fn initialize_reflection_pool(&mut self) {
+            let init_pool_ix = program_ix(
+                crate::accounts::InitializeReflectionPool {
+                    config: self.config,
+                    mint: self.mint,
+                    reflection_pool: pda(&[b"reflection_pool", self.config.as_ref()]),
+                    reflection_authority: pda(&[b"reflection_authority", self.config.as_ref()]),
+                    authority: self.authority.pubkey(),
+                    token_program: self.token_program,
+                    system_program: system_program::ID,
+                    rent: sysvar::rent::ID,
+                },
+                crate::instruction::InitializeReflectionPool {},
+            );
+            self.send_as_authority(&[init_pool_ix], &[]).await.unwrap();
+        }
+
+        async fn enable_trading(&mut self) {
+            let enable_ix = program_ix(
+                crate::accounts::SetTrading {
+                    config: self.config,
+                    authority: self.authority.pubkey(),
+                },
+                crate::instruction::SetTradingEnabled { enabled: true },
+            );
+            self.send_as_authority(&[enable_ix], &[]).await.unwrap();
+        }
+
+        async fn set_exemption(&mut self, owner: Pubkey, flags: ExemptionFlags) {
+            let set_exemption_ix = program_ix(
+                crate::accounts::SetExemption {
+                    config: self.config,
+                    exemption: pda(&[b"exempt", self.config.as_ref(), owner.as_ref()]),
+                    authority: self.authority.pubkey(),
+                    system_program: system_program::ID,
+                },
+                crate::instruction::SetExemption {
+                    owner,
+                    fee_exempt: flags.fee_exempt,
+                    limit_exempt: flags.limit_exempt,
+                    cooldown_exempt: flags.cooldown_exempt,
+                    sniper_whitelisted: flags.sniper_whitelisted,
+                },
+            );
+            self.send_as_authority(&[set_exemption_ix], &[]).await.unwrap();
+        }
+
+        // Program `transfer` signed by the sending wallet, without exemptions
+        fn transfer_ix(&self, from: Pubkey, from_owner: Pubkey, to: Pubkey, to_owner: Pubkey, amount: u64) -> Instruction {
+            program_ix(
+                self.transfer_accounts(from, from_owner, to, to_owner),
+                crate::instruction::Transfer { amount },
+            )
+        }
+
+        fn transfer_accounts(&self, from: Pubkey, from_owner: Pubkey, to: Pubkey, to_owner: Pubkey) -> crate::accounts::Transfer {
+            crate::accounts::Transfer {
+                config: self.config,
+                mint: self.mint,
+                from,
+                to,
+                marketing_wallet: self.marketing_wallet,
+                dev_wallet: self.dev_wallet,
+                reflection_pool: pda(&[b"reflection_pool", self.config.as_ref()]),
+                holder_state: pda(&[b"holder_state", from.as_ref()]),
+                to_holder_state: pda(&[b"holder_state", to.as_ref()]),
+                from_exemption: None,
+                to_exemption: None,
+                from_blocklist: pda(&[b"blocklist", self.config.as_ref(), from_owner.as_ref()]),
+                to_blocklist: pda(&[b"blocklist", self.config.as_ref(), to_owner.as_ref()]),
+                authority: from_owner,
+                token_program: self.token_program,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            }
+        }
+
+        async fn initialize_holder_state(&mut self, token_account: Pubkey) {
+            let init_state_ix = program_ix(
+                crate::accounts::InitializeHolderState {
+                    token_account,
+                    holder_state: pda(&[b"holder_state", token_account.as_ref()]),
+                    payer: self.context.payer.pubkey(),
+                    system_program: system_program::ID,
+                },
+                crate::instruction::InitializeHolderState {},
+            );
+            self.send(&[init_state_ix], &[]).await.unwrap();
+        }
+
+        // Token-2022 `transfer_checked` carrying the hook's extra accounts
+        async fn hooked_transfer_ix(&mut self, from: Pubkey, to: Pubkey, owner: Pubkey, amount: u64) -> Instruction {
+            let to_owner = self.token_owner(to).await;
+            let mut transfer_ix = spl_token_2022::instruction::transfer_checked(
+                &spl_token_2022::ID,
+                &from,
+                &self.mint,
+                &to,
+                &owner,
+                &[],
+                amount,
+                9,
+            ).unwrap();
+            transfer_ix.accounts.extend(self.hook_accounts(from, to, owner, to_owner));
+            transfer_ix
+        }
+
+        // Extra accounts from the meta list, followed by the list and the hook program
+        fn hook_accounts(
+            &self,
+            source: Pubkey,
+            destination: Pubkey,
+            owner: Pubkey,
+            destination_owner: Pubkey,
+        ) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.config, false),
+                AccountMeta::new(pda(&[b"holder_state", source.as_ref()]), false),
+                AccountMeta::new_readonly(pda(&[b"blocklist", self.config.as_ref(), owner.as_ref()]), false),
+                AccountMeta::new(pda(&[b"holder_state", destination.as_ref()]), false),
+                AccountMeta::new_readonly(pda(&[b"blocklist", self.config.as_ref(), destination_owner.as_ref()]), false),
+                AccountMeta::new_readonly(pda(&[b"exempt", self.config.as_ref(), destination_owner.as_ref()]), false),
+                AccountMeta::new_readonly(pda(&[b"exempt", self.config.as_ref(), owner.as_ref()]), false),
+                AccountMeta::new_readonly(pda(&[b"extra-account-metas", self.mint.as_ref()]), false),
+                AccountMeta::new_readonly(crate::ID, false),
+            ]
+        }
+
+        async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+            let account = self.context.banks_client.get_account(address).await.unwrap().unwrap();
+            T::try_deserialize(&mut account.data.as_slice()).unwrap()
+        }
+
+        async fn config_state(&mut self) -> TokenConfig {
+            self.account(self.config).await
+        }
+
+        async fn token_balance(&mut self, address: Pubkey) -> u64 {
+            let account = self.context.banks_client.get_account(address).await.unwrap().unwrap();
+            StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+                .unwrap()
+                .base
+                .amount
+        }
+
+        async fn token_owner(&mut self, address: Pubkey) -> Pubkey {
+            let account = self.context.banks_client.get_account(address).await.unwrap().unwrap();
+            StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+                .unwrap()
+                .base
+                .owner
+        }
+
+        async fn warp_forward(&mut self, seconds: i64) {
+            let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+            clock.unix_timestamp += seconds;
+            self.context.set_sysvar(&clock);
+        }
+
+        async fn advance_slot(&mut self) {
+            let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+            self.context.warp_to_slot(clock.slot + 1).unwrap();
+        }
+
+        fn governance(&self) -> Pubkey {
+            pda(&[b"governance", self.config.as_ref()])
+        }
+
+        async fn initialize_governance(&mut self) {
+            let init_governance_ix = program_ix(
+                crate::accounts::InitializeGovernance {
+                    config: self.config,
+                    mint: self.mint,
+                    governance: self.governance(),
+                    vote_vault: pda(&[b"vote_vault", self.config.as_ref()]),
+                    authority: self.authority.pubkey(),
+                    token_program: self.token_program,
+                    system_program: system_program::ID,
+                    rent: sysvar::rent::ID,
+                },
+                crate::instruction::InitializeGovernance {},
+            );
+            self.send_as_authority(&[init_governance_ix], &[]).await.unwrap();
+        }
+
+        // Escrow the authority's tokens as voting power
+        async fn deposit_votes(&mut self, token_account: Pubkey, amount: u64) {
+            let deposit_ix = program_ix(
+                crate::accounts::DepositVotes {
+                    config: self.config,
+                    mint: self.mint,
+                    governance: self.governance(),
+                    voter_record: pda(&[b"voter", self.governance().as_ref(), self.authority.pubkey().as_ref()]),
+                    vote_vault: pda(&[b"vote_vault", self.config.as_ref()]),
+                    voter_token_account: token_account,
+                    holder_state: pda(&[b"holder_state", token_account.as_ref()]),
+                    voter: self.authority.pubkey(),
+                    token_program: self.token_program,
+                    system_program: system_program::ID,
+                },
+                crate::instruction::DepositVotes { amount },
+            );
+            self.send_as_authority(&[deposit_ix], &[]).await.unwrap();
+        }
+
+        // Proposal opened by the authority's escrowed votes, at the next index
+        async fn create_proposal(&mut self, action: ProposalAction) -> (Pubkey, Result<(), BanksClientError>) {
+            let governance = self.governance();
+            let state: Governance = self.account(governance).await;
+            let proposal = pda(&[b"proposal", governance.as_ref(), state.proposal_count.to_le_bytes().as_ref()]);
+            let create_ix = program_ix(
+                crate::accounts::CreateProposal {
+                    config: self.config,
+                    governance,
+                    proposal,
+                    voter_record: pda(&[b"voter", governance.as_ref(), self.authority.pubkey().as_ref()]),
+                    proposer: self.authority.pubkey(),
+                    system_program: system_program::ID,
+                },
+                crate::instruction::CreateProposal { action, description_hash: [0; 32] },
+            );
+            (proposal, self.send_as_authority(&[create_ix], &[]).await)
+        }
+
+        // Vote for the proposal with the authority's escrow, then close voting
+        async fn pass_proposal(&mut self, proposal: Pubkey) {
+            let governance = self.governance();
+            let vote_ix = program_ix(
+                crate::accounts::CastVote {
+                    config: self.config,
+                    governance,
+                    proposal,
+                    vote_record: pda(&[b"vote", proposal.as_ref(), self.authority.pubkey().as_ref()]),
+                    voter_record: pda(&[b"voter", governance.as_ref(), self.authority.pubkey().as_ref()]),
+                    voter: self.authority.pubkey(),
+                    system_program: system_program::ID,
+                },
+                crate::instruction::CastVote { support: true },
+            );
+            self.send_as_authority(&[vote_ix], &[]).await.unwrap();
+
+            let state: Governance = self.account(governance).await;
+            self.warp_forward(state.voting_period).await;
+            let finalize_ix = program_ix(
+                crate::accounts::FinalizeProposal { config: self.config, governance, proposal },
+                crate::instruction::FinalizeProposal {},
+            );
+            self.send(&[finalize_ix], &[]).await.unwrap();
+            self.warp_forward(state.execution_delay).await;
+        }
+
+        fn execute_proposal_accounts(&self, proposal: Pubkey) -> crate::accounts::ExecuteProposal {
+            crate::accounts::ExecuteProposal {
+                config: self.config,
+                governance: self.governance(),
+                proposal,
+                treasury: None,
+                blocklist_entry: None,
+                exemption: None,
+                mint: None,
+                fee_authority: None,
+                token_program: None,
+            }
+        }
     }
 
     async fn setup_token() -> (
@@ -160,6 +680,7 @@ mod tests {
             accounts: vec![
                 AccountMeta::new(config, false),
                 AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(mint_authority_address(config), false),
                 AccountMeta::new(authority.pubkey(), true),
                 AccountMeta::new_readonly(spl_token::ID, false),
                 AccountMeta::new_readonly(system_program::ID, false),
@@ -170,6 +691,7 @@ mod tests {
                 symbol: "BIGBALLS".to_string(),
                 decimals: 9,
                 marketing_wallet,
+                dev_wallet: Keypair::new().pubkey(),
             }
             .data(),
         };
@@ -225,6 +747,7 @@ mod tests {
             accounts: vec![
                 AccountMeta::new(config, false),
                 AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(mint_authority_address(config), false),
                 AccountMeta::new(authority.pubkey(), true),
                 AccountMeta::new_readonly(spl_token::ID, false),
                 AccountMeta::new_readonly(system_program::ID, false),
@@ -235,6 +758,7 @@ mod tests {
                 symbol: "BIGBALLS".to_string(),
                 decimals: 9,
                 marketing_wallet,
+                dev_wallet: Keypair::new().pubkey(),
             }
             .data(),
         };
@@ -257,11 +781,14 @@ mod tests {
         assert_eq!(token_config.symbol, "BIGBALLS");
         assert_eq!(token_config.decimals, 9);
         assert_eq!(token_config.marketing_wallet, marketing_wallet);
-        assert_eq!(token_config.trading_enabled, false);
+        assert_eq!(token_config.trading_enabled(), false);
+        assert_eq!(token_config.pause_flags, PAUSE_TRADING);
         assert_eq!(token_config.reflection_fee_bp, 200);
         assert_eq!(token_config.marketing_fee_bp, 150);
         assert_eq!(token_config.burn_fee_bp, 100);
         assert_eq!(token_config.dev_fee_bp, 50);
+        assert_eq!(token_config.daily_buy_limit, token_config.total_supply / 20);
+        assert_eq!(token_config.daily_sell_limit, token_config.total_supply / 50);
     }
 
     #[tokio::test]
@@ -274,6 +801,7 @@ mod tests {
             accounts: vec![
                 AccountMeta::new(config, false),
                 AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(mint_authority_address(config), false),
                 AccountMeta::new(authority.pubkey(), true),
                 AccountMeta::new_readonly(spl_token::ID, false),
                 AccountMeta::new_readonly(system_program::ID, false),
@@ -284,6 +812,7 @@ mod tests {
                 symbol: "BIGBALLS".to_string(),
                 decimals: 9,
                 marketing_wallet: Pubkey::default(),
+                dev_wallet: Keypair::new().pubkey(),
             }
             .data(),
         };
@@ -334,7 +863,7 @@ mod tests {
         // Verify trading is enabled
         let config_account = banks_client.get_account(config).await.unwrap().unwrap();
         let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
-        assert_eq!(token_config.trading_enabled, true);
+        assert_eq!(token_config.trading_enabled(), true);
 
         // Test unauthorized access
         let unauthorized = Keypair::new();
@@ -379,6 +908,7 @@ mod tests {
             program_id: crate::ID,
             accounts: vec![
                 AccountMeta::new(config, false),
+                AccountMeta::new(mint, false),
                 AccountMeta::new(from.pubkey(), false),
                 AccountMeta::new(to.pubkey(), false),
                 AccountMeta::new(marketing_wallet, false),
@@ -430,6 +960,7 @@ mod tests {
             program_id: crate::ID,
             accounts: vec![
                 AccountMeta::new(config, false),
+                AccountMeta::new(mint, false),
                 AccountMeta::new(from.pubkey(), false),
                 AccountMeta::new(to.pubkey(), false),
                 AccountMeta::new(marketing_wallet, false),
@@ -472,10 +1003,14 @@ mod tests {
         // Update fees
         let update_fees_ix = Instruction {
             program_id: crate::ID,
-            accounts: vec![
-                AccountMeta::new(config, false),
-                AccountMeta::new(authority.pubkey(), true),
-            ],
+            accounts: crate::accounts::UpdateFees {
+                config,
+                authority: authority.pubkey(),
+                mint: None,
+                fee_authority: None,
+                token_program: None,
+            }
+            .to_account_metas(None),
             data: bigballs_token::instruction::UpdateFees {
                 reflection_fee_bp: 300,    // 3%
                 marketing_fee_bp: 100,     // 1%
@@ -507,10 +1042,14 @@ mod tests {
         // Test invalid fee update (total > 10%)
         let invalid_update_ix = Instruction {
             program_id: crate::ID,
-            accounts: vec![
-                AccountMeta::new(config, false),
-                AccountMeta::new(authority.pubkey(), true),
-            ],
+            accounts: crate::accounts::UpdateFees {
+                config,
+                authority: authority.pubkey(),
+                mint: None,
+                fee_authority: None,
+                token_program: None,
+            }
+            .to_account_metas(None),
             data: bigballs_token::instruction::UpdateFees {
                 reflection_fee_bp: 500,
                 marketing_fee_bp: 300,
@@ -553,10 +1092,10 @@ mod tests {
             config,
         ).await.unwrap();
 
-        // Get max transaction amount
+        // Get max transaction amount for the launch phase
         let config_account = banks_client.get_account(config).await.unwrap().unwrap();
         let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
-        let max_amount = token_config.max_transaction_amount;
+        let max_amount = token_config.launch_max_transaction_amount;
 
         // Test transfer exceeding limit
         let from = Keypair::new();
@@ -566,6 +1105,7 @@ mod tests {
             program_id: crate::ID,
             accounts: vec![
                 AccountMeta::new(config, false),
+                AccountMeta::new(mint, false),
                 AccountMeta::new(from.pubkey(), false),
                 AccountMeta::new(to.pubkey(), false),
                 AccountMeta::new(marketing_wallet, false),
@@ -622,6 +1162,7 @@ mod tests {
             program_id: crate::ID,
             accounts: vec![
                 AccountMeta::new(config, false),
+                AccountMeta::new(mint, false),
                 AccountMeta::new(from.pubkey(), false),
                 AccountMeta::new(to.pubkey(), false),
                 AccountMeta::new(marketing_wallet, false),
@@ -691,10 +1232,14 @@ mod tests {
         // Update fees
         let update_fees_ix = Instruction {
             program_id: crate::ID,
-            accounts: vec![
-                AccountMeta::new(config, false),
-                AccountMeta::new(authority.pubkey(), true),
-            ],
+            accounts: crate::accounts::UpdateFees {
+                config,
+                authority: authority.pubkey(),
+                mint: None,
+                fee_authority: None,
+                token_program: None,
+            }
+            .to_account_metas(None),
             data: bigballs_token::instruction::UpdateFees {
                 reflection_fee_bp: 300,
                 marketing_fee_bp: 100,
@@ -750,6 +1295,7 @@ mod tests {
                 program_id: crate::ID,
                 accounts: vec![
                     AccountMeta::new(config, false),
+                    AccountMeta::new(mint, false),
                     AccountMeta::new(from.pubkey(), false),
                     AccountMeta::new(to.pubkey(), false),
                     AccountMeta::new(marketing_wallet, false),
@@ -847,6 +1393,7 @@ mod tests {
                 program_id: crate::ID,
                 accounts: vec![
                     AccountMeta::new(config, false),
+                    AccountMeta::new(mint, false),
                     AccountMeta::new(from.pubkey(), false),
                     AccountMeta::new(to.pubkey(), false),
                     AccountMeta::new(marketing_wallet, false),
@@ -912,4 +1459,2088 @@ mod tests {
         assert!(banks_client.get_latest_blockhash().await.is_ok());
         println!("✅ Test environment successfully initialized");
     }
-} 
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_transfer_burns_fee_share() {
+        let mut token = TestToken::new().await;
+        token.initialize_reflection_pool().await;
+        let source = token.mint_supply().await;
+        token.enable_trading().await;
+
+        let recipient = Keypair::new();
+        let destination = token.create_token_account(&recipient.pubkey()).await;
+        let amount = 1_000_000;
+
+        let authority = token.authority.pubkey();
+        let transfer_ix = token.transfer_ix(source, authority, destination, recipient.pubkey(), amount);
+        token.send_as_authority(&[transfer_ix], &[]).await.unwrap();
+
+        // The burn share left circulation and was tracked on the config
+        let token_config = token.config_state().await;
+        let expected_burn = amount * token_config.burn_fee_bp as u64 / 10000;
+        let expected_fee = amount * token_config.total_fee_bp() as u64 / 10000;
+        assert_eq!(token_config.total_burned, expected_burn);
+        assert_eq!(token.token_balance(destination).await, amount - expected_fee);
+        assert_eq!(
+            token.token_balance(source).await,
+            token_config.total_supply - amount
+        );
+    }
+
+    #[tokio::test]
+    async fn test_zero_claim_checkpoints_holder() {
+        let mut token = TestToken::new().await;
+        token.initialize_reflection_pool().await;
+        let holder_token_account = token.mint_supply().await;
+        let holder_state = pda(&[b"holder_state", holder_token_account.as_ref()]);
+
+        // A holder with nothing accrued still records its checkpoint
+        let claim_ix = program_ix(
+            crate::accounts::ClaimReflections {
+                config: token.config,
+                mint: token.mint,
+                holder_token_account,
+                holder_state,
+                reflection_pool: pda(&[b"reflection_pool", token.config.as_ref()]),
+                reflection_authority: pda(&[b"reflection_authority", token.config.as_ref()]),
+                holder: token.authority.pubkey(),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            crate::instruction::ClaimReflections {},
+        );
+        token.send_as_authority(&[claim_ix], &[]).await.unwrap();
+
+        let state: HolderState = token.account(holder_state).await;
+        assert!(state.initialized);
+        assert_eq!(state.pending_reflections, 0);
+        assert_eq!(state.checkpoint_balance, token.token_balance(holder_token_account).await);
+    }
+
+    #[tokio::test]
+    async fn test_set_dev_wallet() {
+        let (mut banks_client, payer, authority, config, mint, marketing_wallet) = 
+            setup_token().await;
+
+        initialize_token(
+            &mut banks_client,
+            &payer,
+            &authority,
+            config,
+            mint,
+            marketing_wallet,
+        ).await.unwrap();
+
+        // Update the dev wallet
+        let new_dev_wallet = Keypair::new().pubkey();
+        let set_dev_wallet_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new(authority.pubkey(), true),
+            ],
+            data: bigballs_token::instruction::SetDevWallet {
+                dev_wallet: new_dev_wallet,
+            }
+            .data(),
+        };
+
+        let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[set_dev_wallet_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let config_account = banks_client.get_account(config).await.unwrap().unwrap();
+        let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert_eq!(token_config.dev_wallet, new_dev_wallet);
+
+        // Default pubkey is rejected
+        let invalid_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new(authority.pubkey(), true),
+            ],
+            data: bigballs_token::instruction::SetDevWallet {
+                dev_wallet: Pubkey::default(),
+            }
+            .data(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[invalid_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+
+        let result = banks_client.process_transaction(transaction).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_exemptions_follow_the_trader() {
+        let mut token = TestToken::new().await;
+        token.initialize_reflection_pool().await;
+        let source = token.mint_supply().await;
+        token.enable_trading().await;
+
+        // Exempt a wallet from fees and limits
+        let wallet = Keypair::new();
+        let destination = token.create_token_account(&wallet.pubkey()).await;
+        let exemption = pda(&[b"exempt", token.config.as_ref(), wallet.pubkey().as_ref()]);
+        let set_exemption_ix = program_ix(
+            crate::accounts::SetExemption {
+                config: token.config,
+                exemption,
+                authority: token.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            crate::instruction::SetExemption {
+                owner: wallet.pubkey(),
+                fee_exempt: true,
+                limit_exempt: true,
+                cooldown_exempt: false,
+                sniper_whitelisted: false,
+            },
+        );
+        token.send_as_authority(&[set_exemption_ix], &[]).await.unwrap();
+
+        // Sending to an exempt wallet still pays the sender's fees
+        let authority = token.authority.pubkey();
+        let to_exempt_ix = program_ix(
+            crate::accounts::Transfer {
+                to_exemption: Some(exemption),
+                ..token.transfer_accounts(source, authority, destination, wallet.pubkey())
+            },
+            crate::instruction::Transfer { amount: 1_000_000 },
+        );
+        token.send_as_authority(&[to_exempt_ix], &[]).await.unwrap();
+        assert_eq!(token.token_balance(destination).await, 950_000);
+
+        // The exempt wallet sends without fees
+        let recipient = Pubkey::new_unique();
+        let recipient_account = token.create_token_account(&recipient).await;
+        let fund_wallet_ix = system_instruction::transfer(
+            &token.context.payer.pubkey(),
+            &wallet.pubkey(),
+            1_000_000_000,
+        );
+        let from_exempt_ix = program_ix(
+            crate::accounts::Transfer {
+                from_exemption: Some(exemption),
+                ..token.transfer_accounts(destination, wallet.pubkey(), recipient_account, recipient)
+            },
+            crate::instruction::Transfer { amount: 100_000 },
+        );
+        token.send(&[fund_wallet_ix, from_exempt_ix], &[&wallet]).await.unwrap();
+        assert_eq!(token.token_balance(recipient_account).await, 100_000);
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_exemption_keeps_per_slot_limit() {
+        let mut token = TestToken::new().await;
+        token.initialize_reflection_pool().await;
+        let source = token.mint_supply().await;
+        token.enable_trading().await;
+
+        let wallet = Keypair::new();
+        let wallet_account = token.create_token_account(&wallet.pubkey()).await;
+        let exemption = pda(&[b"exempt", token.config.as_ref(), wallet.pubkey().as_ref()]);
+        let set_exemption_ix = program_ix(
+            crate::accounts::SetExemption {
+                config: token.config,
+                exemption,
+                authority: token.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            crate::instruction::SetExemption {
+                owner: wallet.pubkey(),
+                fee_exempt: false,
+                limit_exempt: false,
+                cooldown_exempt: true,
+                sniper_whitelisted: false,
+            },
+        );
+        let authority = token.authority.pubkey();
+        let fund_ix = token.transfer_ix(source, authority, wallet_account, wallet.pubkey(), 1_000_000);
+        token.send_as_authority(&[set_exemption_ix, fund_ix], &[]).await.unwrap();
+        token.advance_slot().await;
+
+        // Cooldown-exempt wallets still send at most once per slot
+        let recipient = Pubkey::new_unique();
+        let recipient_account = token.create_token_account(&recipient).await;
+        let send_ix = |amount: u64| program_ix(
+            crate::accounts::Transfer {
+                from_exemption: Some(exemption),
+                ..token.transfer_accounts(wallet_account, wallet.pubkey(), recipient_account, recipient)
+            },
+            crate::instruction::Transfer { amount },
+        );
+        let fund_wallet_ix = system_instruction::transfer(&token.context.payer.pubkey(), &wallet.pubkey(), 1_000_000_000);
+        let instructions = [fund_wallet_ix.clone(), send_ix(100_000), send_ix(200_000)];
+        let result = token.send(&instructions, &[&wallet]).await;
+        assert_custom_error(result, BigBallsError::OneTransferPerSlot);
+
+        token.send(&[fund_wallet_ix, send_ix(100_000)], &[&wallet]).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_amm_pool_registry() {
+        let mut token = TestToken::new().await;
+
+        // Register an AMM pool token account
+        let pool = Pubkey::new_unique();
+        let add_pool_ix = program_ix(
+            crate::accounts::UpdateAmmPools { config: token.config, authority: token.authority.pubkey() },
+            crate::instruction::AddAmmPool { pool },
+        );
+        token.send_as_authority(&[add_pool_ix], &[]).await.unwrap();
+
+        let token_config = token.config_state().await;
+        assert_eq!(token_config.amm_pools, vec![pool]);
+
+        // Unauthorized signers cannot remove pools
+        let unauthorized = Keypair::new();
+        let remove_pool_ix = program_ix(
+            crate::accounts::UpdateAmmPools { config: token.config, authority: unauthorized.pubkey() },
+            crate::instruction::RemoveAmmPool { pool },
+        );
+        let result = token.send(&[remove_pool_ix], &[&unauthorized]).await;
+        assert_custom_error(result, BigBallsError::Unauthorized);
+    }
+
+    #[test]
+    fn test_transfer_direction_and_cooldowns() {
+        let pool = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+        let other_wallet = Pubkey::new_unique();
+        let config = TokenConfig {
+            amm_pools: vec![pool],
+            buy_cooldown: 300,
+            sell_cooldown: 1800,
+            transaction_cooldown: 30,
+            ..Default::default()
+        };
+
+        assert_eq!(config.transfer_direction(&pool, &wallet), TransferDirection::Buy);
+        assert_eq!(config.transfer_direction(&wallet, &pool), TransferDirection::Sell);
+        assert_eq!(config.transfer_direction(&wallet, &other_wallet), TransferDirection::WalletToWallet);
+
+        let limits = config.active_limits(0);
+        assert_eq!(config.cooldown_for(TransferDirection::Buy, &limits), config.buy_cooldown);
+        assert_eq!(config.cooldown_for(TransferDirection::Sell, &limits), config.sell_cooldown);
+        assert_eq!(
+            config.cooldown_for(TransferDirection::WalletToWallet, &limits),
+            config.transaction_cooldown
+        );
+    }
+
+    #[tokio::test]
+    async fn test_launch_phase_transition() {
+        let (mut banks_client, payer, authority, config, mint, marketing_wallet) = 
+            setup_token().await;
+
+        initialize_token(
+            &mut banks_client,
+            &payer,
+            &authority,
+            config,
+            mint,
+            marketing_wallet,
+        ).await.unwrap();
+
+        enable_trading(
+            &mut banks_client,
+            &payer,
+            &authority,
+            config,
+        ).await.unwrap();
+
+        let config_account = banks_client.get_account(config).await.unwrap().unwrap();
+        let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert!(token_config.trading_enabled_at > 0);
+
+        // Launch limits apply during the first 24 hours
+        let launch_limits = token_config.active_limits(token_config.trading_enabled_at);
+        assert_eq!(launch_limits.max_transaction_amount, token_config.total_supply / 1000);
+        assert_eq!(launch_limits.max_wallet_amount, token_config.total_supply / 100);
+        assert_eq!(launch_limits.transaction_cooldown, 60);
+
+        // Post-launch limits apply automatically afterwards
+        let post_launch_limits = token_config.active_limits(
+            token_config.trading_enabled_at + token_config.launch_phase_duration,
+        );
+        assert_eq!(post_launch_limits.max_transaction_amount, token_config.total_supply / 100);
+        assert_eq!(post_launch_limits.max_wallet_amount, token_config.total_supply * 3 / 100);
+        assert_eq!(post_launch_limits.transaction_cooldown, 30);
+    }
+
+    #[tokio::test]
+    async fn test_update_limits() {
+        let (mut banks_client, payer, authority, config, mint, marketing_wallet) = 
+            setup_token().await;
+
+        initialize_token(
+            &mut banks_client,
+            &payer,
+            &authority,
+            config,
+            mint,
+            marketing_wallet,
+        ).await.unwrap();
+
+        let config_account = banks_client.get_account(config).await.unwrap().unwrap();
+        let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        let total_supply = token_config.total_supply;
+
+        // Update limits
+        let update_limits_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new(authority.pubkey(), true),
+            ],
+            data: bigballs_token::instruction::UpdateLimits {
+                max_transaction_amount: total_supply / 50,  // 2%
+                max_wallet_amount: total_supply / 25,       // 4%
+                buy_cooldown: 120,
+                sell_cooldown: 600,
+                transaction_cooldown: 15,
+            }
+            .data(),
+        };
+
+        let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[update_limits_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let config_account = banks_client.get_account(config).await.unwrap().unwrap();
+        let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert_eq!(token_config.max_transaction_amount, total_supply / 50);
+        assert_eq!(token_config.max_wallet_amount, total_supply / 25);
+        assert_eq!(token_config.buy_cooldown, 120);
+        assert_eq!(token_config.sell_cooldown, 600);
+        assert_eq!(token_config.transaction_cooldown, 15);
+
+        // Limits below the floor are rejected
+        let invalid_update_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new(authority.pubkey(), true),
+            ],
+            data: bigballs_token::instruction::UpdateLimits {
+                max_transaction_amount: 1,
+                max_wallet_amount: 1,
+                buy_cooldown: 120,
+                sell_cooldown: 600,
+                transaction_cooldown: 15,
+            }
+            .data(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[invalid_update_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+
+        let result = banks_client.process_transaction(transaction).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_two_step_authority_transfer() {
+        let (mut banks_client, payer, authority, config, mint, marketing_wallet) = 
+            setup_token().await;
+
+        initialize_token(
+            &mut banks_client,
+            &payer,
+            &authority,
+            config,
+            mint,
+            marketing_wallet,
+        ).await.unwrap();
+
+        // Propose a new authority
+        let new_authority = Keypair::new();
+        let propose_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new(authority.pubkey(), true),
+            ],
+            data: bigballs_token::instruction::ProposeAuthority {
+                new_authority: new_authority.pubkey(),
+            }
+            .data(),
+        };
+
+        // Accept it with the proposed key
+        let accept_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new_readonly(new_authority.pubkey(), true),
+            ],
+            data: bigballs_token::instruction::AcceptAuthority {}.data(),
+        };
+
+        let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[propose_ix, accept_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &authority, &new_authority],
+            recent_blockhash,
+        );
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let config_account = banks_client.get_account(config).await.unwrap().unwrap();
+        let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert_eq!(token_config.authority, new_authority.pubkey());
+        assert_eq!(token_config.pending_authority, Pubkey::default());
+
+        // Renounce, after which admin instructions are rejected
+        let renounce_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new_readonly(new_authority.pubkey(), true),
+            ],
+            data: bigballs_token::instruction::RenounceAuthority {}.data(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[renounce_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &new_authority],
+            recent_blockhash,
+        );
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let config_account = banks_client.get_account(config).await.unwrap().unwrap();
+        let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert_eq!(token_config.authority, Pubkey::default());
+
+        let result = enable_trading(
+            &mut banks_client,
+            &payer,
+            &new_authority,
+            config,
+        ).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_exemptions() {
+        let mut token = TestToken::new().await;
+
+        // Exempt the treasury from fees and limits but not cooldowns
+        let treasury = Pubkey::new_unique();
+        let exemption = pda(&[b"exempt", token.config.as_ref(), treasury.as_ref()]);
+        let set_exemption_ix = |authority: Pubkey| program_ix(
+            crate::accounts::SetExemption {
+                config: token.config,
+                exemption,
+                authority,
+                system_program: system_program::ID,
+            },
+            crate::instruction::SetExemption {
+                owner: treasury,
+                fee_exempt: true,
+                limit_exempt: true,
+                cooldown_exempt: false,
+                sniper_whitelisted: false,
+            },
+        );
+
+        // Only the authority can grant exemptions
+        let unauthorized = Keypair::new();
+        let transfer_lamports_ix = system_instruction::transfer(
+            &token.context.payer.pubkey(),
+            &unauthorized.pubkey(),
+            1_000_000_000,
+        );
+        let unauthorized_ix = set_exemption_ix(unauthorized.pubkey());
+        let result = token.send(&[transfer_lamports_ix, unauthorized_ix], &[&unauthorized]).await;
+        assert_custom_error(result, BigBallsError::Unauthorized);
+
+        let authority_ix = set_exemption_ix(token.authority.pubkey());
+        token.send_as_authority(&[authority_ix], &[]).await.unwrap();
+
+        let exemption_state: Exemption = token.account(exemption).await;
+        assert_eq!(exemption_state.owner, treasury);
+        assert!(exemption_state.fee_exempt);
+        assert!(exemption_state.limit_exempt);
+        assert!(!exemption_state.cooldown_exempt);
+
+        // Removing the exemption closes the PDA
+        let remove_exemption_ix = program_ix(
+            crate::accounts::RemoveExemption {
+                config: token.config,
+                exemption,
+                authority: token.authority.pubkey(),
+            },
+            crate::instruction::RemoveExemption { owner: treasury },
+        );
+        token.send_as_authority(&[remove_exemption_ix], &[]).await.unwrap();
+        assert!(token.context.banks_client.get_account(exemption).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_initialize_token_2022() {
+        let (mut banks_client, payer, authority, config, _, marketing_wallet) = 
+            setup_token().await;
+
+        let mint = Keypair::new();
+        let fee_authority = Pubkey::find_program_address(
+            &[b"fee_authority", config.as_ref()],
+            &crate::ID,
+        ).0;
+
+        let init_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new(mint.pubkey(), true),
+                AccountMeta::new_readonly(fee_authority, false),
+                AccountMeta::new_readonly(mint_authority_address(config), false),
+                AccountMeta::new(authority.pubkey(), true),
+                AccountMeta::new_readonly(spl_token_2022::ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data: bigballs_token::instruction::InitializeToken2022 {
+                name: "Big Balls".to_string(),
+                symbol: "BIGBALLS".to_string(),
+                decimals: 9,
+                marketing_wallet,
+                dev_wallet: Keypair::new().pubkey(),
+            }
+            .data(),
+        };
+
+        let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[init_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &mint, &authority],
+            recent_blockhash,
+        );
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // Verify the config records Token-2022 mode
+        let config_account = banks_client.get_account(config).await.unwrap().unwrap();
+        let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert_eq!(token_config.token_program, spl_token_2022::ID);
+        assert_eq!(token_config.mint, mint.pubkey());
+
+        // Verify the mint carries both extensions
+        let mint_account = banks_client.get_account(mint.pubkey()).await.unwrap().unwrap();
+        let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_account.data).unwrap();
+        let fee_config = mint_state.get_extension::<TransferFeeConfig>().unwrap();
+        assert_eq!(
+            u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points),
+            token_config.total_fee_bp()
+        );
+        assert_eq!(Option::<Pubkey>::from(fee_config.withdraw_withheld_authority), Some(fee_authority));
+        let hook = mint_state.get_extension::<TransferHook>().unwrap();
+        assert_eq!(Option::<Pubkey>::from(hook.program_id), Some(crate::ID));
+        assert_eq!(Option::<Pubkey>::from(hook.authority), None);
+    }
+
+    #[tokio::test]
+    async fn test_update_fees_syncs_token_2022_transfer_fee() {
+        let mut token = TestToken::new_token_2022().await;
+        let update_fees_ix = |fee_accounts: Option<(Pubkey, Pubkey, Pubkey)>| program_ix(
+            crate::accounts::UpdateFees {
+                config: token.config,
+                authority: token.authority.pubkey(),
+                mint: fee_accounts.map(|accounts| accounts.0),
+                fee_authority: fee_accounts.map(|accounts| accounts.1),
+                token_program: fee_accounts.map(|accounts| accounts.2),
+            },
+            crate::instruction::UpdateFees {
+                reflection_fee_bp: 300,
+                marketing_fee_bp: 100,
+                burn_fee_bp: 50,
+                dev_fee_bp: 50,
+            },
+        );
+
+        // The on-chain fee can't be left stale, so the fee accounts are required
+        let missing_accounts_ix = update_fees_ix(None);
+        let sync_ix = update_fees_ix(Some((
+            token.mint,
+            pda(&[b"fee_authority", token.config.as_ref()]),
+            spl_token_2022::ID,
+        )));
+        let result = token.send_as_authority(&[missing_accounts_ix], &[]).await;
+        assert_custom_error(result, BigBallsError::TransferFeeAccountsRequired);
+        token.send_as_authority(&[sync_ix], &[]).await.unwrap();
+
+        // The new fee takes effect from the next epoch
+        let mint_account = token.context.banks_client.get_account(token.mint).await.unwrap().unwrap();
+        let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_account.data).unwrap();
+        let fee_config = mint_state.get_extension::<TransferFeeConfig>().unwrap();
+        assert_eq!(u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points), 500);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_hook_rejects_direct_execute() {
+        let mut token = TestToken::new_token_2022().await;
+        let source = token.mint_supply().await;
+        token.initialize_holder_state(source).await;
+        let destination_owner = Pubkey::new_unique();
+        let destination = token.create_token_account(&destination_owner).await;
+
+        // Calling the hook outside of a Token-2022 transfer must fail
+        let execute_ix = Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::Execute {
+                source,
+                mint: token.mint,
+                destination,
+                owner: token.authority.pubkey(),
+                extra_account_meta_list: pda(&[b"extra-account-metas", token.mint.as_ref()]),
+                config: token.config,
+                holder_state: pda(&[b"holder_state", source.as_ref()]),
+                owner_blocklist: pda(&[b"blocklist", token.config.as_ref(), token.authority.pubkey().as_ref()]),
+                destination_holder_state: pda(&[b"holder_state", destination.as_ref()]),
+                destination_blocklist: pda(&[b"blocklist", token.config.as_ref(), destination_owner.as_ref()]),
+                destination_exemption: pda(&[b"exempt", token.config.as_ref(), destination_owner.as_ref()]),
+                owner_exemption: pda(&[b"exempt", token.config.as_ref(), token.authority.pubkey().as_ref()]),
+            }
+            .to_account_metas(None),
+            data: TransferHookInstruction::Execute { amount: 1_000_000 }.pack(),
+        };
+        let result = token.send(&[execute_ix], &[]).await;
+        assert_custom_error(result, BigBallsError::NotTransferring);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_hook_rate_limits_buys() {
+        let mut token = TestToken::new_token_2022().await;
+        let supply = token.mint_supply().await;
+        token.initialize_holder_state(supply).await;
+        let total_supply = token.config_state().await.total_supply;
+
+        // Register and seed a pool; the deployer is held to the same rules as
+        // everyone else, so it needs a limit exemption to seed above max-tx
+        token.enable_trading().await;
+        let deployer = token.authority.pubkey();
+        let pool_owner = Keypair::new();
+        let pool = token.create_token_account(&pool_owner.pubkey()).await;
+        token.initialize_holder_state(pool).await;
+        let add_pool_ix = program_ix(
+            crate::accounts::UpdateAmmPools { config: token.config, authority: deployer },
+            crate::instruction::AddAmmPool { pool },
+        );
+        token.send_as_authority(&[add_pool_ix], &[]).await.unwrap();
+        let seed_ix = token.hooked_transfer_ix(supply, pool, deployer, total_supply / 100).await;
+        let result = token.send_as_authority(&[seed_ix.clone()], &[]).await;
+        assert_custom_error(result, BigBallsError::ExceedsMaxTransaction);
+        token.set_exemption(deployer, ExemptionFlags { limit_exempt: true, ..Default::default() }).await;
+        token.send_as_authority(&[seed_ix], &[]).await.unwrap();
+
+        // A buyer without holder state cannot buy straight from the pool
+        let buyer_wallet = Pubkey::new_unique();
+        let buyer = token.create_token_account(&buyer_wallet).await;
+        let buy_amount = total_supply / 10_000;
+        let buy_ix = token.hooked_transfer_ix(pool, buyer, pool_owner.pubkey(), buy_amount).await;
+        let result = token.send(&[buy_ix.clone()], &[&pool_owner]).await;
+        assert_custom_error(result, BigBallsError::HolderStateRequired);
+
+        // Right after launch the hook rejects buys from non-whitelisted wallets
+        token.initialize_holder_state(buyer).await;
+        let result = token.send(&[buy_ix.clone()], &[&pool_owner]).await;
+        assert_custom_error(result, BigBallsError::SniperBlocked);
+
+        // The whitelist is its own flag, keyed by the buyer's wallet like every exemption
+        let whitelist_ix = program_ix(
+            crate::accounts::SetExemption {
+                config: token.config,
+                exemption: pda(&[b"exempt", token.config.as_ref(), buyer_wallet.as_ref()]),
+                authority: token.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            crate::instruction::SetExemption {
+                owner: buyer_wallet,
+                fee_exempt: false,
+                limit_exempt: false,
+                cooldown_exempt: false,
+                sniper_whitelisted: true,
+            },
+        );
+        token.send_as_authority(&[whitelist_ix], &[]).await.unwrap();
+        token.send(&[buy_ix], &[&pool_owner]).await.unwrap();
+        let buyer_state: HolderState = token.account(pda(&[b"holder_state", buyer.as_ref()])).await;
+        assert!(buyer_state.last_buy > 0);
+
+        // The buy cooldown is tracked on the buyer, not the pool
+        token.advance_slot().await;
+        let buy_ix = token.hooked_transfer_ix(pool, buyer, pool_owner.pubkey(), buy_amount).await;
+        let result = token.send(&[buy_ix], &[&pool_owner]).await;
+        assert_custom_error(result, BigBallsError::CooldownNotElapsed);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_hook_applies_owner_exemptions() {
+        let mut token = TestToken::new_token_2022().await;
+        let supply = token.mint_supply().await;
+        token.initialize_holder_state(supply).await;
+        let total_supply = token.config_state().await.total_supply;
+        token.enable_trading().await;
+
+        // The deployer is limit-exempt to fund the sender above the launch max-tx
+        let deployer = token.authority.pubkey();
+        token.set_exemption(deployer, ExemptionFlags { limit_exempt: true, ..Default::default() }).await;
+        let sender = Keypair::new();
+        let source = token.create_token_account(&sender.pubkey()).await;
+        token.initialize_holder_state(source).await;
+        let fund_ix = token.hooked_transfer_ix(supply, source, deployer, total_supply / 100).await;
+        token.send_as_authority(&[fund_ix], &[]).await.unwrap();
+
+        // Without an exemption the sender is held to the launch max-tx
+        let destination = token.create_token_account(&Pubkey::new_unique()).await;
+        let amount = total_supply / 500;
+        let transfer_ix = token.hooked_transfer_ix(source, destination, sender.pubkey(), amount).await;
+        let result = token.send(&[transfer_ix.clone()], &[&sender]).await;
+        assert_custom_error(result, BigBallsError::ExceedsMaxTransaction);
+
+        // Limit and cooldown exemptions lift max-tx and the cooldown, not the per-slot limit
+        let flags = ExemptionFlags { limit_exempt: true, cooldown_exempt: true, ..Default::default() };
+        token.set_exemption(sender.pubkey(), flags).await;
+        token.send(&[transfer_ix.clone()], &[&sender]).await.unwrap();
+        let transfer_ix = token.hooked_transfer_ix(source, destination, sender.pubkey(), amount - 1).await;
+        let result = token.send(&[transfer_ix.clone()], &[&sender]).await;
+        assert_custom_error(result, BigBallsError::OneTransferPerSlot);
+        token.advance_slot().await;
+        token.send(&[transfer_ix], &[&sender]).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_transfer_hook_checks_destination_blocklist() {
+        let mut token = TestToken::new_token_2022().await;
+        let supply = token.mint_supply().await;
+        token.initialize_holder_state(supply).await;
+        let total_supply = token.config_state().await.total_supply;
+
+        // Rewriting the meta list in place keeps hooked transfers working
+        let update_list_ix = program_ix(
+            crate::accounts::UpdateExtraAccountMetaList {
+                config: token.config,
+                mint: token.mint,
+                extra_account_meta_list: pda(&[b"extra-account-metas", token.mint.as_ref()]),
+                authority: token.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            crate::instruction::UpdateExtraAccountMetaList {},
+        );
+        token.send_as_authority(&[update_list_ix], &[]).await.unwrap();
+
+        let sender = Keypair::new();
+        let source = token.create_token_account(&sender.pubkey()).await;
+        token.initialize_holder_state(source).await;
+        token.enable_trading().await;
+        let fund_ix = token.hooked_transfer_ix(supply, source, token.authority.pubkey(), total_supply / 1000).await;
+        token.send_as_authority(&[fund_ix], &[]).await.unwrap();
+
+        // Blocking the recipient's wallet covers its token accounts, as in `transfer`
+        let recipient = Pubkey::new_unique();
+        let destination = token.create_token_account(&recipient).await;
+        token.initialize_holder_state(destination).await;
+        let block_ix = program_ix(
+            crate::accounts::BlockAddress {
+                config: token.config,
+                blocklist_entry: pda(&[b"blocklist", token.config.as_ref(), recipient.as_ref()]),
+                authority: token.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            crate::instruction::BlockAddress { address: recipient, reason: 1 },
+        );
+        token.send_as_authority(&[block_ix], &[]).await.unwrap();
+
+        let transfer_ix = token.hooked_transfer_ix(source, destination, sender.pubkey(), total_supply / 10_000).await;
+        let result = token.send(&[transfer_ix], &[&sender]).await;
+        assert_custom_error(result, BigBallsError::AddressBlocked);
+    }
+
+    #[tokio::test]
+    async fn test_mint_initial_supply_once() {
+        let (mut banks_client, payer, authority, config, mint, marketing_wallet) = 
+            setup_token().await;
+
+        initialize_token(
+            &mut banks_client,
+            &payer,
+            &authority,
+            config,
+            mint,
+            marketing_wallet,
+        ).await.unwrap();
+
+        // Mint authority is the program PDA, not the deployer
+        let mint_account = banks_client.get_account(mint).await.unwrap().unwrap();
+        let mint_state = spl_token::state::Mint::unpack(&mint_account.data).unwrap();
+        assert_eq!(mint_state.mint_authority, COption::Some(mint_authority_address(config)));
+        assert_eq!(mint_state.freeze_authority, COption::Some(mint_authority_address(config)));
+
+        let distribution_account = Keypair::new().pubkey();
+        let mint_supply_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(mint_authority_address(config), false),
+                AccountMeta::new(distribution_account, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::ID, false),
+            ],
+            data: bigballs_token::instruction::MintInitialSupply {}.data(),
+        };
+
+        let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[mint_supply_ix.clone()],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            recent_blockhash,
+        );
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let config_account = banks_client.get_account(config).await.unwrap().unwrap();
+        let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert!(token_config.initial_supply_minted);
+
+        // A second mint is rejected
+        let transaction = Transaction::new_signed_with_payer(
+            &[mint_supply_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+
+        let result = banks_client.process_transaction(transaction).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_distribute_initial_supply_requires_full_allocation() {
+        let (mut banks_client, payer, authority, config, mint, marketing_wallet) = 
+            setup_token().await;
+
+        initialize_token(
+            &mut banks_client,
+            &payer,
+            &authority,
+            config,
+            mint,
+            marketing_wallet,
+        ).await.unwrap();
+
+        // Liquidity and dev buckets that only cover 99% of supply
+        let liquidity_account = Keypair::new().pubkey();
+        let dev_account = Keypair::new().pubkey();
+        let allocations = vec![
+            Allocation { destination: liquidity_account, basis_points: 9500 },
+            Allocation { destination: dev_account, basis_points: 400 },
+        ];
+
+        let distribute_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(mint_authority_address(config), false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::ID, false),
+                AccountMeta::new(liquidity_account, false),
+                AccountMeta::new(dev_account, false),
+            ],
+            data: bigballs_token::instruction::DistributeInitialSupply { allocations }.data(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[distribute_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+
+        let result = banks_client.process_transaction(transaction).await;
+        assert!(result.is_err());
+
+        let config_account = banks_client.get_account(config).await.unwrap().unwrap();
+        let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert!(!token_config.initial_supply_minted);
+    }
+
+    #[tokio::test]
+    async fn test_create_vesting_rejects_cliff_after_end() {
+        let (mut banks_client, payer, authority, config, mint, marketing_wallet) = 
+            setup_token().await;
+
+        initialize_token(
+            &mut banks_client,
+            &payer,
+            &authority,
+            config,
+            mint,
+            marketing_wallet,
+        ).await.unwrap();
+
+        let beneficiary = Keypair::new().pubkey();
+        let (vesting_schedule, _) = Pubkey::find_program_address(
+            &[b"vesting", config.as_ref(), beneficiary.as_ref()],
+            &crate::ID,
+        );
+        let (vault, _) = Pubkey::find_program_address(
+            &[b"vesting_vault", vesting_schedule.as_ref()],
+            &crate::ID,
+        );
+        let (vesting_authority, _) = Pubkey::find_program_address(
+            &[b"vesting_authority", config.as_ref()],
+            &crate::ID,
+        );
+        let source = Keypair::new().pubkey();
+
+        // A 180 day cliff on a 90 day schedule is invalid
+        let create_vesting_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(vesting_schedule, false),
+                AccountMeta::new(vesting_authority, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(source, false),
+                AccountMeta::new(authority.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(sysvar::rent::ID, false),
+            ],
+            data: bigballs_token::instruction::CreateVesting {
+                beneficiary,
+                total_amount: 1_000_000,
+                cliff_duration: 180 * 86_400,
+                vesting_duration: 90 * 86_400,
+            }.data(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[create_vesting_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+
+        let result = banks_client.process_transaction(transaction).await;
+        assert!(result.is_err());
+        assert!(banks_client.get_account(vesting_schedule).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_lock_rejects_past_unlock_time() {
+        let (mut banks_client, payer, _authority, _config, mint, _marketing_wallet) = 
+            setup_token().await;
+
+        let owner = Keypair::new();
+        let lock_id: u64 = 0;
+        let (lock_vault, _) = Pubkey::find_program_address(
+            &[b"lock_vault", owner.pubkey().as_ref(), mint.as_ref(), &lock_id.to_le_bytes()],
+            &crate::ID,
+        );
+        let (vault, _) = Pubkey::find_program_address(
+            &[b"lock_vault_tokens", lock_vault.as_ref()],
+            &crate::ID,
+        );
+        let source = Keypair::new().pubkey();
+
+        let lock_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(lock_vault, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(source, false),
+                AccountMeta::new(owner.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(sysvar::rent::ID, false),
+            ],
+            data: bigballs_token::instruction::Lock {
+                lock_id,
+                amount: 1_000_000,
+                unlock_time: 0,
+            }.data(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[lock_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &owner],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+
+        let result = banks_client.process_transaction(transaction).await;
+        assert!(result.is_err());
+        assert!(banks_client.get_account(lock_vault).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_initialize_marketing_vault() {
+        let (mut banks_client, payer, authority, config, mint, marketing_wallet) = 
+            setup_token().await;
+
+        initialize_token(
+            &mut banks_client,
+            &payer,
+            &authority,
+            config,
+            mint,
+            marketing_wallet,
+        ).await.unwrap();
+
+        let (marketing_multisig, _) = Pubkey::find_program_address(
+            &[b"marketing_multisig", config.as_ref()],
+            &crate::ID,
+        );
+        let (marketing_vault, _) = Pubkey::find_program_address(
+            &[b"marketing_vault", config.as_ref()],
+            &crate::ID,
+        );
+        let (marketing_authority, _) = Pubkey::find_program_address(
+            &[b"marketing_authority", config.as_ref()],
+            &crate::ID,
+        );
+        let signers: Vec<Pubkey> = (0..3).map(|_| Keypair::new().pubkey()).collect();
+
+        let init_vault_ix = |threshold: u8| Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(marketing_multisig, false),
+                AccountMeta::new(marketing_authority, false),
+                AccountMeta::new(marketing_vault, false),
+                AccountMeta::new(authority.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(sysvar::rent::ID, false),
+            ],
+            data: bigballs_token::instruction::InitializeMarketingVault {
+                signers: signers.clone(),
+                threshold,
+            }.data(),
+        };
+
+        // Threshold above the signer count is rejected
+        let transaction = Transaction::new_signed_with_payer(
+            &[init_vault_ix(4)],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+        assert!(banks_client.process_transaction(transaction).await.is_err());
+
+        // 2-of-3 succeeds and redirects marketing fees to the vault
+        let transaction = Transaction::new_signed_with_payer(
+            &[init_vault_ix(2)],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let config_account = banks_client.get_account(config).await.unwrap().unwrap();
+        let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert_eq!(token_config.marketing_wallet, marketing_vault);
+
+        let multisig_account = banks_client.get_account(marketing_multisig).await.unwrap().unwrap();
+        let multisig: MarketingMultisig = try_from_slice_unchecked(&multisig_account.data).unwrap();
+        assert_eq!(multisig.signers, signers);
+        assert_eq!(multisig.threshold, 2);
+        assert_eq!(multisig.timelock_threshold, token_config.total_supply / 200);
+        assert_eq!(multisig.timelock_duration, 86_400);
+    }
+
+    #[tokio::test]
+    async fn test_initialize_governance() {
+        let (mut banks_client, payer, authority, config, mint, marketing_wallet) = 
+            setup_token().await;
+
+        initialize_token(
+            &mut banks_client,
+            &payer,
+            &authority,
+            config,
+            mint,
+            marketing_wallet,
+        ).await.unwrap();
+
+        let (governance, _) = Pubkey::find_program_address(
+            &[b"governance", config.as_ref()],
+            &crate::ID,
+        );
+        let (vote_vault, _) = Pubkey::find_program_address(
+            &[b"vote_vault", config.as_ref()],
+            &crate::ID,
+        );
+
+        let init_governance_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(governance, false),
+                AccountMeta::new(vote_vault, false),
+                AccountMeta::new(authority.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(sysvar::rent::ID, false),
+            ],
+            data: bigballs_token::instruction::InitializeGovernance {}.data(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[init_governance_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let config_account = banks_client.get_account(config).await.unwrap().unwrap();
+        let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+
+        let governance_account = banks_client.get_account(governance).await.unwrap().unwrap();
+        let governance_state: Governance = try_from_slice_unchecked(&governance_account.data).unwrap();
+        assert_eq!(governance_state.vote_vault, vote_vault);
+        assert_eq!(governance_state.proposal_threshold, token_config.total_supply / 1000);
+        assert_eq!(governance_state.voting_period, 3 * 86_400);
+        assert_eq!(governance_state.quorum_bp, 1000);
+        assert_eq!(governance_state.approval_bp, 6600);
+        assert_eq!(governance_state.execution_delay, 86_400);
+
+        // Governance does not hold the config authority until a proposal accepts it
+        assert_eq!(token_config.authority, authority.pubkey());
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_votes_requires_deposit() {
+        let (mut banks_client, payer, authority, config, mint, marketing_wallet) = 
+            setup_token().await;
+
+        initialize_token(
+            &mut banks_client,
+            &payer,
+            &authority,
+            config,
+            mint,
+            marketing_wallet,
+        ).await.unwrap();
+
+        let (governance, _) = Pubkey::find_program_address(
+            &[b"governance", config.as_ref()],
+            &crate::ID,
+        );
+        let (vote_vault, _) = Pubkey::find_program_address(
+            &[b"vote_vault", config.as_ref()],
+            &crate::ID,
+        );
+
+        let init_governance_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(governance, false),
+                AccountMeta::new(vote_vault, false),
+                AccountMeta::new(authority.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(sysvar::rent::ID, false),
+            ],
+            data: bigballs_token::instruction::InitializeGovernance {}.data(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[init_governance_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // A voter with no escrowed tokens has no VoterRecord to withdraw from
+        let voter = Keypair::new();
+        let voter_token_account = Keypair::new().pubkey();
+        let (voter_record, _) = Pubkey::find_program_address(
+            &[b"voter", governance.as_ref(), voter.pubkey().as_ref()],
+            &crate::ID,
+        );
+        let (holder_state, _) = Pubkey::find_program_address(
+            &[b"holder_state", voter_token_account.as_ref()],
+            &crate::ID,
+        );
+
+        let withdraw_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new_readonly(config, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new_readonly(governance, false),
+                AccountMeta::new(voter_record, false),
+                AccountMeta::new(vote_vault, false),
+                AccountMeta::new(voter_token_account, false),
+                AccountMeta::new(holder_state, false),
+                AccountMeta::new_readonly(voter.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::ID, false),
+            ],
+            data: bigballs_token::instruction::WithdrawVotes { amount: 1 }.data(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[withdraw_ix],
+            Some(&payer.pubkey()),
+            &[&payer, &voter],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+        assert!(banks_client.process_transaction(transaction).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_governance_proposals_require_escrowed_stake() {
+        let mut token = TestToken::new().await;
+        let holder = token.mint_supply().await;
+        token.initialize_governance().await;
+
+        // Tokens in a wallet are not a proposal stake
+        let (_, result) = token.create_proposal(ProposalAction::AcceptAuthority).await;
+        assert!(result.is_err());
+
+        let total_supply = token.config_state().await.total_supply;
+        token.deposit_votes(holder, total_supply / 5).await;
+
+        let governance = token.governance();
+        let propose_ix = program_ix(
+            crate::accounts::ProposeAuthority {
+                config: token.config,
+                authority: token.authority.pubkey(),
+            },
+            crate::instruction::ProposeAuthority { new_authority: governance },
+        );
+        token.send_as_authority(&[propose_ix], &[]).await.unwrap();
+
+        let (proposal, result) = token.create_proposal(ProposalAction::AcceptAuthority).await;
+        result.unwrap();
+
+        // The same escrow can't back a second open proposal
+        let (_, result) = token.create_proposal(ProposalAction::AcceptAuthority).await;
+        assert_custom_error(result, BigBallsError::ProposalAlreadyActive);
+
+        token.pass_proposal(proposal).await;
+        let execute_ix = program_ix(
+            token.execute_proposal_accounts(proposal),
+            crate::instruction::ExecuteProposal {},
+        );
+        token.send(&[execute_ix], &[]).await.unwrap();
+        assert_eq!(token.config_state().await.authority, governance);
+
+        // Once voting has closed the stake can back the next proposal, and
+        // admin actions remain reachable through governance
+        let dev_wallet = Pubkey::new_unique();
+        let (proposal, result) = token.create_proposal(ProposalAction::SetDevWallet { dev_wallet }).await;
+        result.unwrap();
+        token.pass_proposal(proposal).await;
+        let execute_ix = program_ix(
+            token.execute_proposal_accounts(proposal),
+            crate::instruction::ExecuteProposal {},
+        );
+        token.send(&[execute_ix], &[]).await.unwrap();
+        assert_eq!(token.config_state().await.dev_wallet, dev_wallet);
+    }
+
+    #[tokio::test]
+    async fn test_manual_burn_cap_and_notice() {
+        let mut token = TestToken::new().await;
+        let holder = token.mint_supply().await;
+        let treasury = pda(&[b"treasury", token.config.as_ref()]);
+        let treasury_vault = pda(&[b"treasury_vault", token.config.as_ref()]);
+
+        let init_treasury_ix = program_ix(
+            crate::accounts::InitializeTreasury {
+                config: token.config,
+                mint: token.mint,
+                treasury,
+                treasury_vault,
+                authority: token.authority.pubkey(),
+                token_program: token.token_program,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            },
+            crate::instruction::InitializeTreasury {},
+        );
+        token.send_as_authority(&[init_treasury_ix], &[]).await.unwrap();
+
+        // Burns are only scheduled by governance once it holds the authority
+        token.initialize_governance().await;
+        let total_supply = token.config_state().await.total_supply;
+        token.deposit_votes(holder, total_supply / 5).await;
+        let propose_ix = program_ix(
+            crate::accounts::ProposeAuthority {
+                config: token.config,
+                authority: token.authority.pubkey(),
+            },
+            crate::instruction::ProposeAuthority { new_authority: token.governance() },
+        );
+        token.send_as_authority(&[propose_ix], &[]).await.unwrap();
+        let (proposal, result) = token.create_proposal(ProposalAction::AcceptAuthority).await;
+        result.unwrap();
+        token.pass_proposal(proposal).await;
+        let execute_ix = program_ix(
+            token.execute_proposal_accounts(proposal),
+            crate::instruction::ExecuteProposal {},
+        );
+        token.send(&[execute_ix], &[]).await.unwrap();
+
+        let burn_cap = total_supply / 100;
+        let execute_accounts = |token: &TestToken, proposal: Pubkey| {
+            let mut accounts = token.execute_proposal_accounts(proposal);
+            accounts.treasury = Some(treasury);
+            accounts
+        };
+
+        // More than 1% of supply is rejected when the proposal executes
+        let (proposal, result) = token.create_proposal(ProposalAction::ScheduleBurn { amount: burn_cap + 1 }).await;
+        result.unwrap();
+        token.pass_proposal(proposal).await;
+        let execute_ix = program_ix(execute_accounts(&token, proposal), crate::instruction::ExecuteProposal {});
+        assert_custom_error(token.send(&[execute_ix], &[]).await, BigBallsError::ExceedsManualBurnCap);
+
+        let (proposal, result) = token.create_proposal(ProposalAction::ScheduleBurn { amount: burn_cap }).await;
+        result.unwrap();
+        token.pass_proposal(proposal).await;
+        let execute_ix = program_ix(execute_accounts(&token, proposal), crate::instruction::ExecuteProposal {});
+        token.send(&[execute_ix], &[]).await.unwrap();
+
+        let treasury_state: Treasury = token.account(treasury).await;
+        assert_eq!(treasury_state.pending_burn_amount, burn_cap);
+
+        // Executing before the 24h notice has elapsed fails
+        let fund_ix = spl_token::instruction::transfer(
+            &spl_token::ID,
+            &holder,
+            &treasury_vault,
+            &token.authority.pubkey(),
+            &[],
+            burn_cap,
+        ).unwrap();
+        token.send_as_authority(&[fund_ix], &[]).await.unwrap();
+        let execute_burn_ix = program_ix(
+            crate::accounts::ExecuteBurn {
+                config: token.config,
+                mint: token.mint,
+                treasury,
+                treasury_vault,
+                token_program: token.token_program,
+            },
+            crate::instruction::ExecuteBurn {},
+        );
+        let result = token.send(&[execute_burn_ix.clone()], &[]).await;
+        assert_custom_error(result, BigBallsError::TimelockNotElapsed);
+
+        token.warp_forward(MANUAL_BURN_NOTICE).await;
+        token.send(&[execute_burn_ix], &[]).await.unwrap();
+        assert_eq!(token.config_state().await.total_burned, burn_cap);
+        assert_eq!(token.token_balance(treasury_vault).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_requires_price_oracle() {
+        let mut token = TestToken::new().await;
+
+        let token_config = token.config_state().await;
+        assert_eq!(token_config.paused_until, 0);
+        assert_eq!(token_config.pause_reason, PAUSE_REASON_NONE);
+
+        // The crank cannot run until the authority creates the oracle
+        let pool_authority = Pubkey::new_unique();
+        let pool_token_account = token.create_token_account(&pool_authority).await;
+        let pool_quote_account = token.create_token_account(&pool_authority).await;
+        let check_ix = program_ix(
+            crate::accounts::CheckCircuitBreaker {
+                config: token.config,
+                price_oracle: pda(&[b"price_oracle", token.config.as_ref()]),
+                pool_token_account,
+                pool_quote_account,
+            },
+            crate::instruction::CheckCircuitBreaker {},
+        );
+        let result = token.send(&[check_ix], &[]).await;
+        assert_custom_error(result, anchor_lang::error::ErrorCode::AccountNotInitialized);
+    }
+
+    #[tokio::test]
+    async fn test_guardian_emergency_shutdown() {
+        let mut token = TestToken::new().await;
+        let guardian_set = pda(&[b"guardian_set", token.config.as_ref()]);
+        let guardians: Vec<Keypair> = (0..5).map(|_| Keypair::new()).collect();
+        let guardian_keys: Vec<Pubkey> = guardians.iter().map(|k| k.pubkey()).collect();
+        let init_guardians_ix = |token: &TestToken, threshold: u8, timelock_duration: i64| program_ix(
+            crate::accounts::InitializeGuardianSet {
+                config: token.config,
+                guardian_set,
+                authority: token.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            crate::instruction::InitializeGuardianSet {
+                guardians: guardian_keys.clone(),
+                threshold,
+                timelock_duration,
+            },
+        );
+
+        // Anything short of 3-of-5 behind a 24h timelock is rejected
+        let ix = init_guardians_ix(&token, 2, MIN_GUARDIAN_TIMELOCK);
+        assert_custom_error(token.send_as_authority(&[ix], &[]).await, BigBallsError::InvalidSignerSet);
+        let ix = init_guardians_ix(&token, 3, 0);
+        assert_custom_error(token.send_as_authority(&[ix], &[]).await, BigBallsError::InvalidTimelock);
+        let ix = init_guardians_ix(&token, 3, MIN_GUARDIAN_TIMELOCK);
+        token.send_as_authority(&[ix], &[]).await.unwrap();
+
+        let emergency_action = pda(&[b"emergency_action", guardian_set.as_ref(), &0u64.to_le_bytes()]);
+        let propose_ix = program_ix(
+            crate::accounts::ProposeEmergencyAction {
+                config: token.config,
+                guardian_set,
+                emergency_action,
+                guardian: guardians[0].pubkey(),
+                system_program: system_program::ID,
+            },
+            crate::instruction::ProposeEmergencyAction {
+                kind: EmergencyActionKind::Shutdown,
+                reason_hash: [7u8; 32],
+                reason_uri: "https://example.com/incident".to_string(),
+            },
+        );
+        let fund_ix = system_instruction::transfer(&token.context.payer.pubkey(), &guardians[0].pubkey(), 1_000_000_000);
+        token.send(&[fund_ix, propose_ix], &[&guardians[0]]).await.unwrap();
+
+        let approve_ix = |token: &TestToken, guardian: &Keypair| program_ix(
+            crate::accounts::ApproveEmergencyAction {
+                config: token.config,
+                guardian_set,
+                emergency_action,
+                guardian: guardian.pubkey(),
+            },
+            crate::instruction::ApproveEmergencyAction {},
+        );
+        let execute_ix = program_ix(
+            crate::accounts::ExecuteEmergencyAction {
+                config: token.config,
+                guardian_set,
+                emergency_action,
+                guardian: guardians[0].pubkey(),
+            },
+            crate::instruction::ExecuteEmergencyAction {},
+        );
+
+        // Two of three approvals are not enough
+        let instructions = [approve_ix(&token, &guardians[1]), execute_ix.clone()];
+        let result = token.send(&instructions, &[&guardians[0], &guardians[1]]).await;
+        assert_custom_error(result, BigBallsError::InsufficientApprovals);
+
+        // Reaching the threshold starts the 24h timelock
+        let instructions = [approve_ix(&token, &guardians[1]), approve_ix(&token, &guardians[2])];
+        token.send(&instructions, &[&guardians[1], &guardians[2]]).await.unwrap();
+        let result = token.send(&[execute_ix.clone()], &[&guardians[0]]).await;
+        assert_custom_error(result, BigBallsError::TimelockNotElapsed);
+
+        token.warp_forward(MIN_GUARDIAN_TIMELOCK).await;
+        token.send(&[execute_ix], &[&guardians[0]]).await.unwrap();
+        assert!(token.config_state().await.emergency_shutdown);
+    }
+
+    #[tokio::test]
+    async fn test_granular_pause_flags() {
+        let (mut banks_client, payer, authority, config, mint, marketing_wallet) = 
+            setup_token().await;
+
+        initialize_token(
+            &mut banks_client,
+            &payer,
+            &authority,
+            config,
+            mint,
+            marketing_wallet,
+        ).await.unwrap();
+
+        let set_pause_flags_ix = |flags: u8| Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(config, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+            ],
+            data: bigballs_token::instruction::SetPauseFlags { flags }.data(),
+        };
+
+        // Unknown bits are rejected
+        let transaction = Transaction::new_signed_with_payer(
+            &[set_pause_flags_ix(1 << 7)],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+        assert!(banks_client.process_transaction(transaction).await.is_err());
+
+        // Pause only sells and governance
+        let transaction = Transaction::new_signed_with_payer(
+            &[set_pause_flags_ix(PAUSE_SELLS | PAUSE_GOVERNANCE)],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let config_account = banks_client.get_account(config).await.unwrap().unwrap();
+        let token_config: TokenConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert!(token_config.is_action_paused(PAUSE_SELLS));
+        assert!(token_config.is_action_paused(PAUSE_GOVERNANCE));
+        assert!(!token_config.is_action_paused(PAUSE_BUYS));
+        assert!(!token_config.is_action_paused(PAUSE_TRANSFERS));
+        assert!(!token_config.is_action_paused(PAUSE_REFLECTION_CLAIMS));
+        assert!(!token_config.trading_enabled());
+
+        // Opening buys starts the launch phase even with sells still paused
+        assert!(token_config.trading_enabled_at > 0);
+    }
+
+    #[tokio::test]
+    async fn test_block_and_unblock_address() {
+        let mut token = TestToken::new().await;
+        token.initialize_reflection_pool().await;
+        let source = token.mint_supply().await;
+        token.enable_trading().await;
+
+        let wallet = Keypair::new();
+        let destination = token.create_token_account(&wallet.pubkey()).await;
+        let blocklist_entry = pda(&[b"blocklist", token.config.as_ref(), wallet.pubkey().as_ref()]);
+
+        let block_ix = program_ix(
+            crate::accounts::BlockAddress {
+                config: token.config,
+                blocklist_entry,
+                authority: token.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            crate::instruction::BlockAddress { address: wallet.pubkey(), reason: 2 },
+        );
+        token.send_as_authority(&[block_ix], &[]).await.unwrap();
+
+        let entry: Blocklist = token.account(blocklist_entry).await;
+        assert_eq!(entry.address, wallet.pubkey());
+        assert!(entry.blocked);
+        assert_eq!(entry.reason, 2);
+
+        // Blocked wallets cannot receive
+        let authority = token.authority.pubkey();
+        let transfer_ix = token.transfer_ix(source, authority, destination, wallet.pubkey(), 1_000_000);
+        let result = token.send_as_authority(&[transfer_ix.clone()], &[]).await;
+        assert_custom_error(result, BigBallsError::AddressBlocked);
+
+        // Only the authority can lift the block
+        let unblock_ix = |authority: Pubkey| program_ix(
+            crate::accounts::UnblockAddress {
+                config: token.config,
+                blocklist_entry,
+                authority,
+            },
+            crate::instruction::UnblockAddress { address: wallet.pubkey() },
+        );
+        let unauthorized = Keypair::new();
+        let result = token.send(&[unblock_ix(unauthorized.pubkey())], &[&unauthorized]).await;
+        assert_custom_error(result, BigBallsError::Unauthorized);
+
+        let authority_ix = unblock_ix(token.authority.pubkey());
+        token.send_as_authority(&[authority_ix], &[]).await.unwrap();
+
+        let entry: Blocklist = token.account(blocklist_entry).await;
+        assert!(!entry.blocked);
+        token.send_as_authority(&[transfer_ix], &[]).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_anti_sniper() {
+        let mut token = TestToken::new().await;
+
+        let token_config = token.config_state().await;
+        assert_eq!(token_config.anti_sniper_slots, 5);
+        assert_eq!(token_config.anti_sniper_fee_bp, 2500);
+        assert!(!token_config.anti_sniper_block);
+        assert!(!token_config.in_anti_sniper_window(0));
+
+        let update_anti_sniper_ix = |anti_sniper_fee_bp: u16| program_ix(
+            crate::accounts::UpdateLimits {
+                config: token.config,
+                authority: token.authority.pubkey(),
+            },
+            crate::instruction::UpdateAntiSniper {
+                anti_sniper_slots: 10,
+                anti_sniper_fee_bp,
+                anti_sniper_block: true,
+            },
+        );
+
+        // Penalty above the 50% cap is rejected
+        let rejected_ix = update_anti_sniper_ix(5001);
+        let result = token.send_as_authority(&[rejected_ix], &[]).await;
+        assert_custom_error(result, BigBallsError::InvalidAntiSniperConfiguration);
+
+        let accepted_ix = update_anti_sniper_ix(1000);
+        token.send_as_authority(&[accepted_ix], &[]).await.unwrap();
+
+        let token_config = token.config_state().await;
+        assert_eq!(token_config.anti_sniper_slots, 10);
+        assert_eq!(token_config.anti_sniper_fee_bp, 1000);
+        assert!(token_config.anti_sniper_block);
+    }
+
+    #[tokio::test]
+    async fn test_update_launch_limits() {
+        let mut token = TestToken::new().await;
+        let total_supply = token.config_state().await.total_supply;
+
+        let update_launch_limits_ix = |launch_phase_duration: i64| program_ix(
+            crate::accounts::UpdateLimits {
+                config: token.config,
+                authority: token.authority.pubkey(),
+            },
+            crate::instruction::UpdateLaunchLimits {
+                launch_phase_duration,
+                launch_max_transaction_amount: total_supply / 500,
+                launch_max_wallet_amount: total_supply / 50,
+                launch_transaction_cooldown: 30,
+            },
+        );
+
+        // Launch phase longer than a week is rejected
+        let rejected_ix = update_launch_limits_ix(MAX_LAUNCH_PHASE_DURATION + 1);
+        let result = token.send_as_authority(&[rejected_ix], &[]).await;
+        assert_custom_error(result, BigBallsError::InvalidLimitConfiguration);
+
+        let accepted_ix = update_launch_limits_ix(2 * SECONDS_PER_DAY);
+        token.send_as_authority(&[accepted_ix], &[]).await.unwrap();
+
+        let token_config = token.config_state().await;
+        assert_eq!(token_config.launch_phase_duration, 2 * SECONDS_PER_DAY);
+        assert_eq!(token_config.launch_max_transaction_amount, total_supply / 500);
+        assert_eq!(token_config.launch_max_wallet_amount, total_supply / 50);
+        assert_eq!(token_config.launch_transaction_cooldown, 30);
+    }
+
+    #[test]
+    fn test_anti_sniper_window() {
+        let mut config = TokenConfig {
+            anti_sniper_slots: 5,
+            ..Default::default()
+        };
+        assert!(!config.in_anti_sniper_window(0));
+
+        config.trading_enabled_at = 1_000;
+        config.trading_enabled_slot = 100;
+        assert!(config.in_anti_sniper_window(100));
+        assert!(config.in_anti_sniper_window(104));
+        assert!(!config.in_anti_sniper_window(105));
+    }
+
+    #[test]
+    fn test_launch_limits_apply_until_phase_ends() {
+        let mut config = TokenConfig {
+            max_transaction_amount: 10_000,
+            max_wallet_amount: 30_000,
+            transaction_cooldown: 30,
+            launch_phase_duration: SECONDS_PER_DAY,
+            launch_max_transaction_amount: 1_000,
+            launch_max_wallet_amount: 10_000,
+            launch_transaction_cooldown: 60,
+            ..Default::default()
+        };
+
+        // Post-launch limits until trading has been enabled
+        assert!(!config.in_launch_phase(0));
+        assert_eq!(config.active_limits(0).max_transaction_amount, 10_000);
+
+        config.trading_enabled_at = 1_000;
+        let launch_limits = config.active_limits(1_000);
+        assert_eq!(launch_limits.max_transaction_amount, 1_000);
+        assert_eq!(launch_limits.max_wallet_amount, 10_000);
+        assert_eq!(launch_limits.transaction_cooldown, 60);
+
+        let post_launch_limits = config.active_limits(1_000 + SECONDS_PER_DAY);
+        assert_eq!(post_launch_limits.max_transaction_amount, 10_000);
+        assert_eq!(post_launch_limits.max_wallet_amount, 30_000);
+        assert_eq!(post_launch_limits.transaction_cooldown, 30);
+    }
+
+    #[test]
+    fn test_require_authority() {
+        let authority = Pubkey::new_unique();
+        let mut config = TokenConfig {
+            authority,
+            ..Default::default()
+        };
+
+        assert!(config.require_authority(&authority).is_ok());
+        assert_eq!(
+            config.require_authority(&Pubkey::new_unique()).unwrap_err(),
+            BigBallsError::Unauthorized.into()
+        );
+
+        config.authority = Pubkey::default();
+        assert_eq!(
+            config.require_authority(&Pubkey::default()).unwrap_err(),
+            BigBallsError::AuthorityRenounced.into()
+        );
+    }
+
+    #[test]
+    fn test_fee_and_supply_helpers() {
+        let config = TokenConfig {
+            total_supply: 1_000_000,
+            total_burned: 100_000,
+            marketing_wallet: Pubkey::new_unique(),
+            dev_wallet: Pubkey::new_unique(),
+            reflection_fee_bp: 200,
+            marketing_fee_bp: 150,
+            burn_fee_bp: 100,
+            dev_fee_bp: 50,
+            ..Default::default()
+        };
+        assert_eq!(config.total_fee_bp(), 500);
+        assert_eq!(config.circulating_supply(), 900_000);
+
+        assert!(config.is_max_wallet_exempt(&config.marketing_wallet));
+        assert!(config.is_max_wallet_exempt(&config.dev_wallet));
+        assert!(!config.is_max_wallet_exempt(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_settle_reflections() {
+        let mut holder_state = HolderState::default();
+
+        // A fresh state only checkpoints; earlier reflections are not claimable
+        holder_state.settle_reflections(1_000, 5 * REFLECTION_PRECISION).unwrap();
+        assert_eq!(holder_state.pending_reflections, 0);
+        assert_eq!(holder_state.reflection_checkpoint, 5 * REFLECTION_PRECISION);
+
+        holder_state.settle_reflections(1_000, 7 * REFLECTION_PRECISION).unwrap();
+        assert_eq!(holder_state.pending_reflections, 2_000);
+
+        // Vote-escrowed tokens keep earning
+        holder_state.escrowed_amount = 500;
+        holder_state.settle_reflections(1_000, 8 * REFLECTION_PRECISION).unwrap();
+        assert_eq!(holder_state.pending_reflections, 3_500);
+
+        // Tokens that arrived since the checkpoint don't earn until the next one
+        holder_state.settle_reflections(10_000, 9 * REFLECTION_PRECISION).unwrap();
+        assert_eq!(holder_state.pending_reflections, 5_000);
+        assert_eq!(holder_state.checkpoint_balance, 10_000);
+
+        // A balance that dropped since the checkpoint earns on what is left
+        holder_state.escrowed_amount = 0;
+        holder_state.settle_reflections(100, 10 * REFLECTION_PRECISION).unwrap();
+        assert_eq!(holder_state.pending_reflections, 5_100);
+    }
+
+    #[test]
+    fn test_daily_volume_caps() {
+        let mut holder_state = HolderState::default();
+
+        holder_state.record_buy(600, 0, 1_000).unwrap();
+        assert_eq!(
+            holder_state.record_buy(500, 60, 1_000).unwrap_err(),
+            BigBallsError::ExceedsDailyBuyLimit.into()
+        );
+        holder_state.record_sell(1_000, 0, 1_000).unwrap();
+        assert_eq!(
+            holder_state.record_sell(1, 60, 1_000).unwrap_err(),
+            BigBallsError::ExceedsDailySellLimit.into()
+        );
+    }
+
+    #[test]
+    fn test_daily_volume_window_rolls() {
+        let mut holder_state = HolderState::default();
+        let start = 1_000 * SECONDS_PER_HOUR;
+
+        // Volume late in one day still counts early the next
+        holder_state.record_sell(600, start + SECONDS_PER_DAY - 60, 1_000).unwrap();
+        assert_eq!(
+            holder_state.record_sell(600, start + SECONDS_PER_DAY + 60, 1_000).unwrap_err(),
+            BigBallsError::ExceedsDailySellLimit.into()
+        );
+        holder_state.record_sell(400, start + SECONDS_PER_DAY + 60, 1_000).unwrap();
+
+        // Each hour's volume drops out once it is more than a day old
+        assert_eq!(
+            holder_state.record_sell(1, start + 2 * SECONDS_PER_DAY - 60, 1_000).unwrap_err(),
+            BigBallsError::ExceedsDailySellLimit.into()
+        );
+        holder_state.record_sell(600, start + 2 * SECONDS_PER_DAY + SECONDS_PER_HOUR, 1_000).unwrap();
+        assert_eq!(holder_state.sell_volume.volume_with(0, start + 3 * SECONDS_PER_DAY).unwrap(), 600);
+
+        // A long gap clears the whole window
+        assert_eq!(holder_state.sell_volume.volume_with(0, start + 10 * SECONDS_PER_DAY).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_marketing_proposal_rules() {
+        let config = TokenConfig {
+            total_supply: 1_000_000,
+            ..Default::default()
+        };
+        let signers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut multisig = MarketingMultisig {
+            config: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            signers: signers.clone(),
+            threshold: 2,
+            timelock_threshold: config.total_supply / 200,
+            timelock_duration: MIN_MARKETING_TIMELOCK,
+            proposal_count: 0,
+        };
+
+        // The timelock can't be shortened below a day or bypassed for large spends
+        let update_timelock = |timelock_threshold, timelock_duration| MarketingAction::UpdateTimelock {
+            timelock_threshold,
+            timelock_duration,
+        };
+        assert_eq!(
+            update_timelock(0, MIN_MARKETING_TIMELOCK - 1).validate(&config).unwrap_err(),
+            BigBallsError::InvalidTimelock.into()
+        );
+        assert_eq!(
+            update_timelock(u64::MAX, MIN_MARKETING_TIMELOCK).validate(&config).unwrap_err(),
+            BigBallsError::InvalidTimelock.into()
+        );
+        assert!(update_timelock(config.total_supply / 100, MIN_MARKETING_TIMELOCK).validate(&config).is_ok());
+
+        // Small spends run on approval; config changes always wait
+        let small_spend = MarketingAction::Spend { recipient: Pubkey::new_unique(), amount: 1 };
+        assert!(!small_spend.is_timelocked(&multisig));
+        assert!(update_timelock(0, MIN_MARKETING_TIMELOCK).is_timelocked(&multisig));
+
+        let mut proposal = MarketingProposal {
+            multisig: Pubkey::new_unique(),
+            index: 0,
+            proposer: signers[0],
+            action: MarketingAction::RotateSigners { signers: signers[1..].to_vec(), threshold: 2 },
+            approvals: Vec::new(),
+            created_at: 0,
+            executable_at: 0,
+            executed: false,
+        };
+        proposal.approve(&multisig, signers[0], 100).unwrap();
+        assert_eq!(proposal.executable_at, 0);
+        proposal.approve(&multisig, signers[1], 200).unwrap();
+        assert_eq!(proposal.executable_at, 200 + MIN_MARKETING_TIMELOCK);
+
+        // Approvals from rotated-out signers stop counting
+        multisig.signers = signers[1..].to_vec();
+        assert_eq!(proposal.valid_approvals(&multisig), 1);
+    }
+
+    #[test]
+    fn test_proposal_action_fits_len() {
+        let key = Pubkey::new_unique();
+        let actions = [
+            ProposalAction::AcceptAuthority,
+            ProposalAction::UpdateFees { reflection_fee_bp: 1, marketing_fee_bp: 1, burn_fee_bp: 1, dev_fee_bp: 1 },
+            ProposalAction::SetTradingEnabled { enabled: true },
+            ProposalAction::SetPauseFlags { flags: 1 },
+            ProposalAction::BlockAddress { address: key, reason: 1 },
+            ProposalAction::UnblockAddress { address: key },
+            ProposalAction::ScheduleBurn { amount: 1 },
+            ProposalAction::ProposeAuthority { new_authority: key },
+            ProposalAction::UpdateLimits {
+                max_transaction_amount: 1,
+                max_wallet_amount: 1,
+                buy_cooldown: 1,
+                sell_cooldown: 1,
+                transaction_cooldown: 1,
+            },
+            ProposalAction::UpdateLaunchLimits {
+                launch_phase_duration: 1,
+                launch_max_transaction_amount: 1,
+                launch_max_wallet_amount: 1,
+                launch_transaction_cooldown: 1,
+            },
+            ProposalAction::UpdateAntiSniper { anti_sniper_slots: 1, anti_sniper_fee_bp: 1, anti_sniper_block: true },
+            ProposalAction::SetDevWallet { dev_wallet: key },
+            ProposalAction::AddAmmPool { pool: key },
+            ProposalAction::RemoveAmmPool { pool: key },
+            ProposalAction::SetExemption {
+                owner: key,
+                fee_exempt: true,
+                limit_exempt: true,
+                cooldown_exempt: true,
+                sniper_whitelisted: true,
+            },
+        ];
+        for action in actions {
+            assert!(action.try_to_vec().unwrap().len() <= ProposalAction::LEN);
+        }
+    }
+
+    #[test]
+    fn test_manual_burn_expiry_and_cancel() {
+        let config = TokenConfig {
+            total_supply: 1_000_000,
+            ..Default::default()
+        };
+        let mut treasury = Treasury {
+            config: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            pending_burn_amount: 0,
+            burn_executable_at: 0,
+            burn_count: 0,
+        };
+        assert_eq!(treasury.cancel_burn(0).unwrap_err(), BigBallsError::NoBurnScheduled.into());
+
+        treasury.schedule_burn(1, &config, 0).unwrap();
+        assert_eq!(
+            treasury.schedule_burn(1, &config, MANUAL_BURN_NOTICE).unwrap_err(),
+            BigBallsError::BurnAlreadyScheduled.into()
+        );
+
+        // A burn left unexecuted past the window no longer blocks the next one
+        let expired_at = MANUAL_BURN_NOTICE + MANUAL_BURN_EXPIRY + 1;
+        assert!(!treasury.is_burn_expired(expired_at - 1));
+        assert!(treasury.is_burn_expired(expired_at));
+        treasury.schedule_burn(2, &config, expired_at).unwrap();
+        assert_eq!(treasury.pending_burn_amount, 2);
+        assert_eq!(treasury.burn_executable_at, expired_at + MANUAL_BURN_NOTICE);
+
+        treasury.cancel_burn(expired_at).unwrap();
+        assert_eq!(treasury.pending_burn_amount, 0);
+        assert!(!treasury.is_burn_expired(expired_at + MANUAL_BURN_NOTICE + MANUAL_BURN_EXPIRY + 1));
+    }
+
+    #[test]
+    fn test_price_oracle_confirms_drops() {
+        let mut oracle = PriceOracle::deserialize(&mut &[0u8; PriceOracle::LEN][..]).unwrap();
+        oracle.drop_threshold_bp = 5000;
+        assert_eq!(oracle.peak_price_since(0), 0);
+        assert_eq!(oracle.recent_price(), None);
+
+        // The ring buffer wraps and drops the oldest observations
+        for i in 0..PRICE_OBSERVATIONS as i64 + 2 {
+            oracle.record(1 + i * MIN_OBSERVATION_INTERVAL, 100);
+        }
+        assert_eq!(oracle.next_index, 2);
+        assert_eq!(oracle.last_observed_at(), 1 + (PRICE_OBSERVATIONS as i64 + 1) * MIN_OBSERVATION_INTERVAL);
+
+        // A single spike or crash doesn't move the sustained prices
+        let mut now = oracle.last_observed_at();
+        now += MIN_OBSERVATION_INTERVAL;
+        oracle.record(now, 1_000);
+        assert_eq!(oracle.peak_price_since(0), 100);
+        now += MIN_OBSERVATION_INTERVAL;
+        oracle.record(now, 1);
+        assert_eq!(oracle.recent_price(), Some(1_000));
+        assert!(!oracle.is_drop(oracle.peak_price_since(0), oracle.recent_price().unwrap()));
+
+        // A drop held across every confirmation trips against the earlier peak
+        for _ in 1..CIRCUIT_BREAKER_CONFIRMATIONS {
+            now += MIN_OBSERVATION_INTERVAL;
+            oracle.record(now, 49);
+        }
+        assert_eq!(oracle.recent_price(), Some(49));
+        assert!(oracle.is_drop(oracle.peak_price_since(0), 49));
+
+        // Exactly 50% below the reference is not more than the threshold
+        assert!(!oracle.is_drop(100, 50));
+        assert!(!oracle.is_drop(0, 0));
+        // Observations before `since` are ignored
+        assert_eq!(oracle.peak_price_since(now - MIN_OBSERVATION_INTERVAL), 0);
+
+        // The pause reason is reset once the pause has run out
+        let mut config = TokenConfig {
+            paused_until: now + MAX_PAUSE_DURATION,
+            pause_reason: PAUSE_REASON_PRICE_DROP,
+            ..Default::default()
+        };
+        config.clear_expired_pause(now);
+        assert_eq!(config.pause_reason, PAUSE_REASON_PRICE_DROP);
+        config.clear_expired_pause(now + MAX_PAUSE_DURATION);
+        assert_eq!(config.pause_reason, PAUSE_REASON_NONE);
+        assert!(!config.is_paused(now));
+    }
+
+    #[test]
+    fn test_emergency_action_expiry() {
+        let mut action = EmergencyAction::deserialize(&mut &[0u8; EmergencyAction::LEN][..]).unwrap();
+        assert!(!action.is_expired(i64::MAX / 2));
+
+        action.executable_at = MIN_GUARDIAN_TIMELOCK;
+        assert!(!action.is_expired(MIN_GUARDIAN_TIMELOCK + EMERGENCY_ACTION_EXPIRY));
+        assert!(action.is_expired(MIN_GUARDIAN_TIMELOCK + EMERGENCY_ACTION_EXPIRY + 1));
+    }
+
+    #[test]
+    fn test_pause_flags_start_launch_and_governance_unpause() {
+        let mut config = TokenConfig {
+            pause_flags: PAUSE_ALL,
+            ..Default::default()
+        };
+
+        config.apply_pause_flags(PAUSE_ALL & !PAUSE_SELLS, 1_000, 50).unwrap();
+        assert_eq!(config.trading_enabled_at, 0);
+
+        // Buys opening is the launch, recorded at the slot passed in
+        config.apply_pause_flags(PAUSE_SELLS | PAUSE_GOVERNANCE, 2_000, 60).unwrap();
+        assert_eq!(config.trading_enabled_at, 2_000);
+        assert_eq!(config.trading_enabled_slot, 60);
+        config.apply_trading_enabled(true, 3_000, 70).unwrap();
+        assert_eq!(config.trading_enabled_slot, 60);
+
+        // Only proposals clearing the governance pause get through it
+        assert!(ProposalAction::SetPauseFlags { flags: PAUSE_SELLS }.lifts_governance_pause());
+        assert!(!ProposalAction::SetPauseFlags { flags: PAUSE_ALL }.lifts_governance_pause());
+        assert!(!ProposalAction::SetTradingEnabled { enabled: true }.lifts_governance_pause());
+    }
+
+    #[test]
+    fn test_pooled_vaults_from_recorded_bumps() {
+        let config_key = Pubkey::new_unique();
+        let (vote_vault, vote_vault_bump) =
+            Pubkey::find_program_address(&[b"vote_vault", config_key.as_ref()], &crate::ID);
+        let mut config = TokenConfig::default();
+
+        // Vaults only count once their creation has recorded the bump
+        assert!(!config.is_pooled_vault(&config_key, &vote_vault));
+        config.vote_vault_bump = Some(vote_vault_bump);
+        assert!(config.is_pooled_vault(&config_key, &vote_vault));
+        assert!(!config.is_pooled_vault(&Pubkey::new_unique(), &vote_vault));
+        assert!(!config.is_pooled_vault(&config_key, &Pubkey::new_unique()));
+    }
+}